@@ -1,5 +1,5 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use kiss_srt::{time::Timestamp, Subtitle};
+use kiss_srt::{Subtitle, Timestamp};
 use oorandom::Rand64;
 
 // Sample text is made up of quotes from _The House of Leaves_ with varying length
@@ -82,6 +82,8 @@ fn gen_subtitles(num_items: u64) -> String {
             start,
             duration,
             text,
+            position: None,
+            id: None,
         });
     }
 
@@ -33,13 +33,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let Args { srt_path } = parse_args();
 
     let bytes = fs::read(&srt_path)?;
-    // BOM-sniffing
-    let text = std::str::from_utf8(if bytes.starts_with(b"\xef\xbb\xbf") {
-        &bytes[3..]
-    } else {
-        &bytes
-    })?;
-    let subtitles = kiss_srt::from_str(text)?;
+    let (subtitles, _encoding) = kiss_srt::from_bytes(&bytes)?;
     println!("{:#?}", subtitles);
 
     Ok(())
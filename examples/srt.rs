@@ -68,14 +68,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let Args { command, srt_path } = parse_args();
 
     let bytes = fs::read(&srt_path)?;
-    // BOM-sniffing
-    let text = std::str::from_utf8(if bytes.starts_with(b"\xef\xbb\xbf") {
-        &bytes[3..]
-    } else {
-        &bytes
-    })?;
-
-    let subtitles = kiss_srt::from_str(text)?;
+    let (subtitles, _encoding) = kiss_srt::from_bytes(&bytes)?;
 
     // Perform the transformation
     let modified: Vec<_> = subtitles
@@ -0,0 +1,372 @@
+//! Conversion to and from [Advanced SubStation Alpha](http://www.tcax.org/docs/ass-specs.htm)
+//! (ASS) and SubStation Alpha (SSA) scripts
+//!
+//! Only the `Dialogue:` events matter when reading a script. Styles, comments, and everything else
+//! gets ignored
+//!
+//! ```
+//! const ASS: &str = r"[Script Info]
+//! ScriptType: v4.00+
+//!
+//! [Events]
+//! Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text
+//! Comment: 0,0:00:00.00,0:00:01.00,Default,,0,0,0,,Ignored
+//! Dialogue: 0,0:00:01.50,0:00:03.00,Default,,0,0,0,,{\i1}Hello{\i0}, world!\NSecond line
+//! ";
+//!
+//! let subtitles = kiss_srt::ass::from_str(ASS).unwrap();
+//! assert_eq!(
+//!     kiss_srt::to_string(&subtitles),
+//!     "1\n00:00:01,500 --> 00:00:03,000\n<i>Hello</i>, world!\nSecond line\n",
+//! );
+//!
+//! // And back again
+//! let ass = kiss_srt::ass::to_string(&subtitles);
+//! assert!(ass.ends_with(
+//!     "Dialogue: 0,0:00:01.50,0:00:03.00,Default,,0,0,0,,{\\i1}Hello{\\i0}, world!\\NSecond line\n",
+//! ));
+//! ```
+
+use std::{fmt, io};
+
+use crate::{
+    error::{Error, Result, Span, TimestampPart},
+    parse::{parse_ascii_num, parse_separator, strip_bom, Cursor, TsResult},
+    tags::{parse_tag, TagKind},
+    Subtitle, Timestamp,
+};
+
+// The format used when a script doesn't have a `Format:` line for its events
+const DEFAULT_FORMAT: Format = Format {
+    start: 1,
+    end: 2,
+    len: 10,
+};
+
+/// Attempts to parse the `Dialogue:` events from the provided ASS/SSA script to a [`Vec`] of
+/// [`Subtitle`]s
+///
+/// The subtitles are sorted by their starting timestamp since scripts often list events out of
+/// order. Line breaks (`\N`) become new lines, the `\i`, `\b`, and `\u` override tags become the
+/// SRT `<i>`, `<b>`, and `<u>` tags, and all other override tags along with any drawings are
+/// stripped. Escaped braces (`\{` and `\}`) become plain braces
+pub fn from_str(script: &str) -> Result<Vec<Subtitle>> {
+    let script = strip_bom(script);
+
+    let mut subtitles = Vec::new();
+    let mut saw_events = false;
+    let mut in_events = false;
+    let mut format = DEFAULT_FORMAT;
+    let mut num_lines = 0;
+    for (line_num, line) in (1..).zip(script.lines()) {
+        num_lines = line_num;
+        if line.starts_with('[') {
+            in_events = line.trim_end().eq_ignore_ascii_case("[Events]");
+            saw_events |= in_events;
+        } else if !in_events {
+            continue;
+        } else if line.starts_with("Format:") {
+            format = Format::parse(line_num, line)?;
+        } else if line.starts_with("Dialogue:") {
+            subtitles.push(parse_dialogue(line_num, line, format)?);
+        }
+    }
+
+    if !saw_events {
+        return Err(Error::missing_ass_events(num_lines + 1));
+    }
+
+    subtitles.sort_by_key(|subtitle| subtitle.start);
+    Ok(subtitles)
+}
+
+gen_renderers! {
+    /// Renders the `subtitles` to a minimal ASS script with a single default style
+    ///
+    /// Braces in the text get escaped as `\{` and `\}`. ASS has no way to escape a backslash though,
+    /// so a `\` right before an `N`, `n`, or `h` comes back as a line break, a space, or a
+    /// non-breaking space respectively
+    fn to_string;
+    /// Renders the `subtitles` to a minimal ASS script into a [`fmt::Write`]
+    fn to_fmt_writer;
+    /// Renders the `subtitles` to a minimal ASS script into an [`io::Write`]
+    fn to_writer;
+    |subtitles| Script(subtitles)
+}
+
+// Where the fields we care about are in each event
+#[derive(Clone, Copy)]
+struct Format {
+    start: usize,
+    end: usize,
+    len: usize,
+}
+
+impl Format {
+    // Of the form 'Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text'
+    fn parse(line_num: usize, line: &str) -> Result<Self> {
+        let fields: Vec<_> = line["Format:".len()..]
+            .split(',')
+            .map(|field| field.trim().to_ascii_lowercase())
+            .collect();
+        let position = |name| fields.iter().position(|field| field == name);
+
+        // The text has to come last since it can contain commas itself
+        match (position("start"), position("end"), position("text")) {
+            (Some(start), Some(end), Some(text)) if text == fields.len() - 1 => Ok(Self {
+                start,
+                end,
+                len: fields.len(),
+            }),
+            _ => Err(Error::invalid_ass_format(
+                line_num,
+                Span::new("Format:".len(), line.len()),
+            )),
+        }
+    }
+}
+
+// Of the form 'Dialogue: 0,0:00:01.00,0:00:02.00,Default,,0,0,0,,Text'
+fn parse_dialogue(line_num: usize, line: &str, format: Format) -> Result<Subtitle> {
+    let mut fields = Vec::with_capacity(format.len);
+    let mut offset = "Dialogue:".len();
+    for field in line[offset..].splitn(format.len, ',') {
+        fields.push((offset, field));
+        offset += field.len() + 1;
+    }
+    if fields.len() < format.len {
+        return Err(Error::invalid_ass_dialogue(
+            line_num,
+            Span::new(line.len(), line.len()),
+        ));
+    }
+
+    let start = parse_field_ts(line_num, line, fields[format.start])?;
+    let (end_offset, end_field) = fields[format.end];
+    let end = parse_field_ts(line_num, line, (end_offset, end_field))?;
+    if end < start {
+        return Err(Error::ts_end_before_start(
+            line_num,
+            Span::new(end_offset, end_offset + end_field.len()),
+        ));
+    }
+
+    let (_, raw_text) = fields[format.len - 1];
+    let mut converted = String::new();
+    push_text(&mut converted, raw_text);
+    // Line breaks at the start or end, or right next to each other, would leave empty lines
+    let mut text = String::with_capacity(converted.len());
+    for line in converted.split('\n').filter(|line| !line.is_empty()) {
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        text.push_str(line);
+    }
+
+    Ok(Subtitle {
+        start,
+        duration: end - start,
+        text,
+        position: None,
+        id: None,
+    })
+}
+
+fn parse_field_ts(
+    line_num: usize,
+    line: &str,
+    (offset, field): (usize, &str),
+) -> Result<Timestamp> {
+    let mut cursor = Cursor::new(line);
+    cursor.pos = offset + (field.len() - field.trim_start().len());
+    let field_end = offset + field.trim_end().len();
+
+    let ts = parse_ts(&mut cursor)
+        .map_err(|(span, part)| Error::invalid_ass_ts(line_num, span, part))?;
+    if cursor.pos != field_end {
+        return Err(Error::invalid_ass_ts(
+            line_num,
+            Span::new(cursor.pos, field_end),
+            TimestampPart::Millis,
+        ));
+    }
+
+    Ok(ts)
+}
+
+// Of the form '1:23:45.67'
+fn parse_ts(cursor: &mut Cursor<'_>) -> TsResult<Timestamp> {
+    let (hours, _) = parse_ascii_num(cursor, 1, 2, 100, TimestampPart::Hours)?;
+    parse_separator(cursor, b':', TimestampPart::HoursSeparator)?;
+    let (minutes, _) = parse_ascii_num(cursor, 2, 2, 60, TimestampPart::Minutes)?;
+    parse_separator(cursor, b':', TimestampPart::MinutesSeparator)?;
+    let (seconds, _) = parse_ascii_num(cursor, 2, 2, 60, TimestampPart::Seconds)?;
+    parse_separator(cursor, b'.', TimestampPart::SecondsSeparator)?;
+    let (centis, _) = parse_ascii_num(cursor, 2, 2, 100, TimestampPart::Millis)?;
+
+    // All of the components were already range checked
+    Ok(
+        Timestamp::new(hours as u8, minutes as u8, seconds as u8, centis * 10)
+            .expect("Components are in range"),
+    )
+}
+
+// Converts line breaks and override tags to their SRT equivalents
+fn push_text(text: &mut String, raw: &str) {
+    let mut drawing = false;
+    let mut rest = raw;
+    while let Some(i) = rest.find(|c| c == '{' || c == '\\') {
+        if !drawing {
+            text.push_str(&rest[..i]);
+        }
+        rest = &rest[i..];
+
+        if rest.starts_with('{') {
+            let end = match rest.find('}') {
+                Some(end) => end,
+                // Not actually an override block
+                None => {
+                    if !drawing {
+                        text.push('{');
+                    }
+                    rest = &rest[1..];
+                    continue;
+                }
+            };
+            for tag in rest[1..end].split('\\').skip(1) {
+                let (name, arg) = tag.split_at(
+                    tag.find(|c: char| !c.is_ascii_alphabetic())
+                        .unwrap_or(tag.len()),
+                );
+                // Bold can also be a weight like `\b700`, so anything but zero turns it on
+                let enabled = arg.bytes().any(|b| b != b'0');
+                match name {
+                    // Drawings are vector graphics, so they have no business in the text
+                    "p" => drawing = enabled,
+                    "i" | "b" | "u" if !drawing => {
+                        text.push('<');
+                        if !enabled {
+                            text.push('/');
+                        }
+                        text.push_str(name);
+                        text.push('>');
+                    }
+                    _ => {}
+                }
+            }
+            rest = &rest[end + 1..];
+        } else {
+            let (replacement, len) = match rest.as_bytes().get(1) {
+                Some(b'N') => ("\n", 2),
+                // Soft line breaks only break with some wrapping styles, so they're a space
+                Some(b'n') => (" ", 2),
+                Some(b'h') => ("\u{a0}", 2),
+                // Escaped braces that would otherwise start or end an override block
+                Some(b'{') => ("{", 2),
+                Some(b'}') => ("}", 2),
+                _ => ("\\", 1),
+            };
+            if !drawing {
+                text.push_str(replacement);
+            }
+            rest = &rest[len..];
+        }
+    }
+
+    if !drawing {
+        text.push_str(rest);
+    }
+}
+
+// An ASS timestamp rounded to the nearest centisecond
+struct AssTimestamp(Timestamp);
+
+impl fmt::Display for AssTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self(ts) = self;
+        let (hours, minutes, seconds, centis) = ts.centi_parts();
+        write!(f, "{}:{:02}:{:02}.{:02}", hours, minutes, seconds, centis)
+    }
+}
+
+struct Script<'a>(&'a [Subtitle]);
+
+impl fmt::Display for Script<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(
+            "\
+[Script Info]
+ScriptType: v4.00+
+PlayResX: 384
+PlayResY: 288
+WrapStyle: 0
+ScaledBorderAndShadow: yes
+
+[V4+ Styles]
+Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, BackColour, \
+Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, BorderStyle, Outline, Shadow, \
+Alignment, MarginL, MarginR, MarginV, Encoding
+Style: Default,Arial,16,&H00FFFFFF,&H000000FF,&H00000000,&H00000000,0,0,0,0,100,100,0,0,1,1,0,2,\
+10,10,10,1
+
+[Events]
+Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text
+",
+        )?;
+
+        for subtitle in self.0 {
+            write!(
+                f,
+                "Dialogue: 0,{},{},Default,,0,0,0,,",
+                AssTimestamp(subtitle.start),
+                AssTimestamp(subtitle.start + subtitle.duration)
+            )?;
+            write_text(f, &subtitle.text)?;
+            f.write_str("\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+// Converts line breaks and tags to their ASS equivalents. Braces get escaped so they don't start
+// override blocks, but there's no escaping a `\` that comes right before an `N`, `n`, or `h`
+fn write_text(f: &mut fmt::Formatter<'_>, text: &str) -> fmt::Result {
+    let mut rest = text;
+    while let Some(i) = rest.find(|c| c == '\n' || c == '<' || c == '{' || c == '}') {
+        f.write_str(&rest[..i])?;
+        rest = &rest[i..];
+
+        let escaped = match rest.as_bytes()[0] {
+            b'\n' => Some("\\N"),
+            b'{' => Some("\\{"),
+            b'}' => Some("\\}"),
+            _ => None,
+        };
+        if let Some(escaped) = escaped {
+            f.write_str(escaped)?;
+            rest = &rest[1..];
+            continue;
+        }
+
+        // ASS colors don't map cleanly from HTML colors, so `<font>` gets dropped
+        match parse_tag(rest) {
+            Some(tag) => {
+                let enabled = if tag.closing { 0 } else { 1 };
+                match tag.kind {
+                    TagKind::Italic => write!(f, "{{\\i{}}}", enabled)?,
+                    TagKind::Bold => write!(f, "{{\\b{}}}", enabled)?,
+                    TagKind::Underline => write!(f, "{{\\u{}}}", enabled)?,
+                    TagKind::Font => {}
+                }
+                rest = &rest[tag.len..];
+            }
+            None => {
+                f.write_str("<")?;
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    f.write_str(rest)
+}
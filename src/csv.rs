@@ -0,0 +1,607 @@
+//! Conversion to and from CSV and TSV spreadsheets for handing subtitles off to translators
+//!
+//! Sheets have an `id,start,end,text` header row followed by a row for each subtitle. Fields that
+//! contain the delimiter, a quote, or a line break get quoted, so multi-line text stays within a
+//! single cell
+//!
+//! ```
+//! const CSV: &str = r#"id,start,end,text
+//! 1,"00:00:01,000","00:00:03,500","Hello, world!
+//! Second line"
+//! 2,"00:00:04,000","00:00:05,000",Goodbye
+//! "#;
+//!
+//! let subtitles = kiss_srt::csv::from_str(CSV).unwrap();
+//! assert_eq!(
+//!     kiss_srt::to_string(&subtitles),
+//!     "\
+//! 1
+//! 00:00:01,000 --> 00:00:03,500
+//! Hello, world!
+//! Second line
+//!
+//! 2
+//! 00:00:04,000 --> 00:00:05,000
+//! Goodbye
+//! ",
+//! );
+//!
+//! // And back again
+//! assert_eq!(kiss_srt::csv::to_string(&subtitles), CSV);
+//! ```
+
+use std::{borrow::Cow, fmt, io};
+
+use crate::{
+    error::{Error, Result, Span, TimestampPart},
+    parse::{error_at, parse_ts, strip_bom, Cursor, TsResult},
+    webvtt, Subtitle, Timestamp,
+};
+
+/// Attempts to parse the provided CSV text to a [`Vec`] of [`Subtitle`]s using the default
+/// [`Options`]
+pub fn from_str(csv: &str) -> Result<Vec<Subtitle>> {
+    Options::new().parse(csv)
+}
+
+gen_renderers! {
+    /// Renders the `subtitles` to CSV using the default [`Options`]
+    fn to_string;
+    /// Renders the `subtitles` to CSV into a [`fmt::Write`] using the default [`Options`]
+    fn to_fmt_writer;
+    /// Renders the `subtitles` to CSV into an [`io::Write`] using the default [`Options`]
+    fn to_writer;
+    |subtitles| Options::new().sheet(subtitles)
+}
+
+/// How the `start` and `end` columns are written
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TimestampFormat {
+    /// SRT timestamps like `00:01:23,456`
+    Srt,
+    /// WebVTT timestamps like `00:01:23.456`
+    ///
+    /// The hours are optional when parsing, so `01:23.456` works too
+    WebVtt,
+    /// A whole number of milliseconds like `83456`
+    Millis,
+    /// A number of seconds like `83.456`
+    ///
+    /// Parsing rounds anything past the milliseconds to the nearest millisecond
+    Seconds,
+}
+
+impl Default for TimestampFormat {
+    fn default() -> Self {
+        Self::Srt
+    }
+}
+
+/// Options for tweaking the layout of the spreadsheet, which apply to both parsing and rendering
+///
+/// ```
+/// use kiss_srt::csv::{Options, TimestampFormat};
+///
+/// const TSV: &str = "\
+/// id\tstart\tend\ttext
+/// 1\t1.5\t3\tHello, world!
+/// ";
+///
+/// let tsv = Options::new()
+///     .delimiter('\t')
+///     .timestamp_format(TimestampFormat::Seconds);
+/// let subtitles = tsv.parse(TSV).unwrap();
+/// assert_eq!(
+///     kiss_srt::to_string(&subtitles),
+///     "1\n00:00:01,500 --> 00:00:03,000\nHello, world!\n",
+/// );
+///
+/// assert_eq!(
+///     tsv.render(&subtitles),
+///     "id\tstart\tend\ttext\n1\t1.500\t3.000\tHello, world!\n",
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Options {
+    delimiter: char,
+    timestamp_format: TimestampFormat,
+    keep_ids: bool,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            timestamp_format: TimestampFormat::default(),
+            keep_ids: false,
+        }
+    }
+}
+
+impl Options {
+    /// The default options which match the behavior of [`from_str()`] and [`to_string()`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the character between fields. A `,` by default, or use `\t` for TSV
+    ///
+    /// # Panics
+    ///
+    /// When the `delimiter` is a `"` or a line break since those can't be told apart from the
+    /// quoting and the rows
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        assert!(
+            delimiter != '"' && delimiter != '\n' && delimiter != '\r',
+            "The delimiter can't be a quote or a line break",
+        );
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Sets how the `start` and `end` columns are written. [`TimestampFormat::Srt`] by default
+    pub fn timestamp_format(mut self, format: TimestampFormat) -> Self {
+        self.timestamp_format = format;
+        self
+    }
+
+    /// Sets whether parsing keeps the `id` column in [`Subtitle::id`]
+    ///
+    /// Off by default which leaves the IDs to be inferred when rendering like
+    /// [`ParseOptions::keep_ids()`][crate::ParseOptions::keep_ids] does. IDs still have to be
+    /// numbers either way
+    ///
+    /// ```
+    /// use kiss_srt::csv::Options;
+    ///
+    /// const CSV: &str = "\
+    /// id,start,end,text
+    /// 10,\"00:00:00,000\",\"00:00:01,000\",Vendor IDs
+    /// ";
+    ///
+    /// assert_eq!(Options::new().parse(CSV).unwrap()[0].id, None);
+    /// assert_eq!(Options::new().keep_ids(true).parse(CSV).unwrap()[0].id, Some(10));
+    /// ```
+    pub fn keep_ids(mut self, keep: bool) -> Self {
+        self.keep_ids = keep;
+        self
+    }
+
+    /// Attempts to parse the provided text to a [`Vec`] of [`Subtitle`]s using these options
+    ///
+    /// The columns are found by their names in the header row, ignoring case, and any columns
+    /// besides `id`, `start`, `end`, and `text` are ignored. The `id` column is optional and only
+    /// gets used with [`Options::keep_ids()`]. Blank rows are skipped
+    ///
+    /// Blank lines within the text are dropped since a blank line would end the cue early once
+    /// it's rendered as SRT
+    pub fn parse(&self, csv: &str) -> Result<Vec<Subtitle>> {
+        let csv = strip_bom(csv);
+        let mut rows = Rows {
+            csv,
+            pos: 0,
+            delimiter: self.delimiter,
+        };
+
+        let header = loop {
+            match rows.next() {
+                Some(row) => {
+                    let row = row?;
+                    if !row.is_blank() {
+                        break row;
+                    }
+                }
+                None => return Err(Error::missing_csv_column(1, Span::default())),
+            }
+        };
+        let column = |name: &str| {
+            header
+                .fields
+                .iter()
+                .position(|field| field.value.trim().eq_ignore_ascii_case(name))
+        };
+        let columns = match (column("start"), column("end"), column("text")) {
+            (Some(start), Some(end), Some(text)) => Columns {
+                id: column("id"),
+                start,
+                end,
+                text,
+            },
+            _ => {
+                return Err(error_at(
+                    csv,
+                    Span::new(header.start, header.end),
+                    Error::missing_csv_column,
+                ))
+            }
+        };
+
+        let mut subtitles = Vec::new();
+        for row in rows {
+            let row = row?;
+            if row.is_blank() {
+                continue;
+            }
+            subtitles.push(self.parse_row(csv, &row, columns)?);
+        }
+
+        Ok(subtitles)
+    }
+
+    fn parse_row(&self, csv: &str, row: &Row<'_>, columns: Columns) -> Result<Subtitle> {
+        let field = |column: usize| {
+            row.fields
+                .get(column)
+                .ok_or_else(|| error_at(csv, Span::new(row.end, row.end), Error::missing_csv_field))
+        };
+
+        let id = match columns.id.and_then(|column| row.fields.get(column)) {
+            Some(field) => {
+                let (value, span) = field.trimmed();
+                if value.is_empty() {
+                    None
+                } else if value.bytes().all(|b| b.is_ascii_digit()) {
+                    value.parse::<u32>().ok().filter(|_| self.keep_ids)
+                } else {
+                    return Err(error_at(csv, span, Error::invalid_id));
+                }
+            }
+            None => None,
+        };
+
+        let (start_invalid, end_invalid): (TsError, TsError) = match self.timestamp_format {
+            TimestampFormat::Srt => (Error::invalid_ts_start, Error::invalid_ts_end),
+            TimestampFormat::WebVtt => (Error::invalid_vtt_ts, Error::invalid_vtt_ts),
+            TimestampFormat::Millis | TimestampFormat::Seconds => {
+                (Error::invalid_numeric_ts, Error::invalid_numeric_ts)
+            }
+        };
+        let start = self.parse_ts(csv, field(columns.start)?, start_invalid)?;
+        let end_field = field(columns.end)?;
+        let end = self.parse_ts(csv, end_field, end_invalid)?;
+        if end < start {
+            return Err(error_at(
+                csv,
+                end_field.trimmed().1,
+                Error::ts_end_before_start,
+            ));
+        }
+
+        let raw_text = &field(columns.text)?.value;
+        let mut text = String::with_capacity(raw_text.len());
+        for line in raw_text.lines().filter(|line| !line.trim().is_empty()) {
+            if !text.is_empty() {
+                text.push('\n');
+            }
+            text.push_str(line);
+        }
+
+        Ok(Subtitle {
+            start,
+            duration: end - start,
+            text,
+            position: None,
+            id,
+        })
+    }
+
+    fn parse_ts(&self, csv: &str, field: &Field<'_>, invalid: TsError) -> Result<Timestamp> {
+        let (value, span) = field.trimmed();
+        let numeric = |parse: fn(&str) -> Option<Timestamp>, part| {
+            parse(value).ok_or((Span::new(0, value.len()), part))
+        };
+        let parsed: TsResult<Timestamp> = match self.timestamp_format {
+            TimestampFormat::Srt | TimestampFormat::WebVtt => {
+                let mut cursor = Cursor::new(value);
+                let ts = if self.timestamp_format == TimestampFormat::Srt {
+                    parse_ts(&mut cursor, false)
+                } else {
+                    webvtt::parse_ts(&mut cursor)
+                };
+                ts.and_then(|ts| {
+                    if cursor.is_empty() {
+                        Ok(ts)
+                    } else {
+                        Err((cursor.rest(), TimestampPart::Millis))
+                    }
+                })
+            }
+            TimestampFormat::Millis => numeric(parse_millis, TimestampPart::Millis),
+            TimestampFormat::Seconds => numeric(parse_seconds, TimestampPart::Seconds),
+        };
+
+        parsed.map_err(|(ts_span, part)| {
+            let ts_span = Span::new(span.start + ts_span.start, span.start + ts_span.end);
+            error_at(csv, ts_span, |line, span| invalid(line, span, part))
+        })
+    }
+
+    gen_render_methods! {
+        /// Renders the `subtitles` using these options
+        ///
+        /// Positions get dropped since there's no column for them. The IDs are inferred as
+        /// index + 1 unless an explicit [`Subtitle::id`] is set
+        fn sheet
+    }
+
+    fn sheet<'a>(&'a self, subtitles: &'a [Subtitle]) -> Sheet<'a> {
+        Sheet {
+            options: self,
+            subtitles,
+        }
+    }
+}
+
+type TsError = fn(usize, Span, TimestampPart) -> Error;
+
+// Of the form '83456'
+fn parse_millis(value: &str) -> Option<Timestamp> {
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    Timestamp::checked_from_millis(value.parse().ok()?)
+}
+
+// Of the form '83' or '83.456' where extra fractional digits get rounded
+fn parse_seconds(value: &str) -> Option<Timestamp> {
+    let (whole, fraction) = match value.find('.') {
+        Some(i) => (&value[..i], Some(&value[i + 1..])),
+        None => (value, None),
+    };
+    let is_num = |num: &str| !num.is_empty() && num.bytes().all(|b| b.is_ascii_digit());
+    if !is_num(whole) {
+        return None;
+    }
+
+    let mut millis = whole.parse::<u32>().ok()?.checked_mul(1_000)?;
+    if let Some(fraction) = fraction {
+        if !is_num(fraction) {
+            return None;
+        }
+        let digits = fraction.as_bytes();
+        let mut fraction_millis = (0..3).fold(0, |acc, i| {
+            acc * 10 + digits.get(i).map_or(0, |&digit| u32::from(digit - b'0'))
+        });
+        if digits.get(3).map_or(false, |&digit| digit >= b'5') {
+            fraction_millis += 1;
+        }
+        millis = millis.checked_add(fraction_millis)?;
+    }
+
+    Timestamp::checked_from_millis(millis)
+}
+
+#[derive(Clone, Copy)]
+struct Columns {
+    id: Option<usize>,
+    start: usize,
+    end: usize,
+    text: usize,
+}
+
+#[derive(Clone, Debug)]
+struct Field<'a> {
+    value: Cow<'a, str>,
+    // The byte offset of the value within the whole text, which skips an opening quote
+    start: usize,
+}
+
+impl Field<'_> {
+    // The value without surrounding whitespace along with its span within the whole text
+    fn trimmed(&self) -> (&str, Span) {
+        let start_trimmed = self.value.trim_start();
+        let start = self.start + self.value.len() - start_trimmed.len();
+        let value = start_trimmed.trim_end();
+
+        (value, Span::new(start, start + value.len()))
+    }
+}
+
+#[derive(Clone, Debug)]
+struct Row<'a> {
+    fields: Vec<Field<'a>>,
+    // The byte offsets of the row within the whole text, excluding the line break
+    start: usize,
+    end: usize,
+}
+
+impl Row<'_> {
+    // Blank lines and rows of empty fields like `,,,` that spreadsheets like to leave behind
+    fn is_blank(&self) -> bool {
+        self.fields
+            .iter()
+            .all(|field| field.value.trim().is_empty())
+    }
+}
+
+// Splits the text into rows following RFC 4180 where quoted fields can hold line breaks
+struct Rows<'a> {
+    csv: &'a str,
+    pos: usize,
+    delimiter: char,
+}
+
+impl<'a> Rows<'a> {
+    fn quoted_field(&mut self) -> Result<Field<'a>> {
+        let open = self.pos;
+        let start = open + 1;
+        let mut value = Cow::Borrowed("");
+        let mut pos = start;
+        loop {
+            let quote = match self.csv[pos..].find('"') {
+                Some(i) => pos + i,
+                None => {
+                    return Err(error_at(
+                        self.csv,
+                        Span::new(open, start),
+                        Error::unclosed_csv_quote,
+                    ))
+                }
+            };
+            let chunk = &self.csv[pos..quote];
+            if self.csv[quote + 1..].starts_with('"') {
+                // A doubled quote is an escaped quote
+                value.to_mut().push_str(chunk);
+                value.to_mut().push('"');
+                pos = quote + 2;
+            } else {
+                value = match value {
+                    Cow::Borrowed(_) => Cow::Borrowed(&self.csv[start..quote]),
+                    Cow::Owned(mut owned) => {
+                        owned.push_str(chunk);
+                        Cow::Owned(owned)
+                    }
+                };
+                self.pos = quote + 1;
+                break;
+            }
+        }
+
+        let rest = &self.csv[self.pos..];
+        if !(rest.is_empty()
+            || rest.starts_with(self.delimiter)
+            || rest.starts_with('\n')
+            || rest.starts_with("\r\n"))
+        {
+            let len = rest.chars().next().map_or(0, char::len_utf8);
+            return Err(error_at(
+                self.csv,
+                Span::new(self.pos, self.pos + len),
+                Error::invalid_csv_quote,
+            ));
+        }
+
+        Ok(Field { value, start })
+    }
+
+    fn unquoted_field(&mut self) -> Field<'a> {
+        let start = self.pos;
+        let delimiter = self.delimiter;
+        let end = self.csv[start..]
+            .find(|c| c == delimiter || c == '\n')
+            .map_or(self.csv.len(), |i| start + i);
+        self.pos = end;
+        let value = &self.csv[start..end];
+        let value = if self.csv[end..].starts_with('\n') {
+            value.trim_end_matches('\r')
+        } else {
+            value
+        };
+
+        Field {
+            value: Cow::Borrowed(value),
+            start,
+        }
+    }
+}
+
+impl<'a> Iterator for Rows<'a> {
+    type Item = Result<Row<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos >= self.csv.len() {
+            return None;
+        }
+
+        let start = self.pos;
+        let mut fields = Vec::new();
+        loop {
+            let field = if self.csv[self.pos..].starts_with('"') {
+                match self.quoted_field() {
+                    Ok(field) => field,
+                    Err(err) => {
+                        // There's no telling where the next row starts
+                        self.pos = self.csv.len();
+                        return Some(Err(err));
+                    }
+                }
+            } else {
+                self.unquoted_field()
+            };
+            fields.push(field);
+
+            let rest = &self.csv[self.pos..];
+            if rest.starts_with(self.delimiter) {
+                self.pos += self.delimiter.len_utf8();
+                continue;
+            }
+            let end = self.pos;
+            let end = if rest.starts_with("\r\n") {
+                self.pos += 2;
+                end
+            } else if rest.starts_with('\n') {
+                self.pos += 1;
+                // An unquoted field already dropped the `\r`
+                end - usize::from(self.csv[..end].ends_with('\r'))
+            } else {
+                end
+            };
+
+            return Some(Ok(Row { fields, start, end }));
+        }
+    }
+}
+
+// A timestamp in the configured format
+struct CsvTimestamp(Timestamp, TimestampFormat);
+
+impl fmt::Display for CsvTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self(ts, format) = self;
+        match format {
+            TimestampFormat::Srt => write!(f, "{}", ts),
+            TimestampFormat::WebVtt => write!(f, "{}", webvtt::VttTimestamp(*ts)),
+            TimestampFormat::Millis => write!(f, "{}", ts.total_millis()),
+            TimestampFormat::Seconds => write!(f, "{}.{:03}", ts.total_seconds(), ts.millis()),
+        }
+    }
+}
+
+// A single field that gets quoted when it has to be
+struct CsvField<'a>(&'a str, char);
+
+impl fmt::Display for CsvField<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self(value, delimiter) = *self;
+        if value.contains(|c| c == delimiter || c == '"' || c == '\n' || c == '\r') {
+            write!(f, "\"{}\"", value.replace('"', "\"\""))
+        } else {
+            f.write_str(value)
+        }
+    }
+}
+
+struct Sheet<'a> {
+    options: &'a Options,
+    subtitles: &'a [Subtitle],
+}
+
+impl fmt::Display for Sheet<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Options {
+            delimiter,
+            timestamp_format,
+            ..
+        } = *self.options;
+        writeln!(f, "id{0}start{0}end{0}text", delimiter)?;
+
+        for (i, subtitle) in (1..).zip(self.subtitles) {
+            let id = subtitle.id.unwrap_or(i);
+            let start = CsvTimestamp(subtitle.start, timestamp_format).to_string();
+            let end =
+                CsvTimestamp(subtitle.start + subtitle.duration, timestamp_format).to_string();
+            writeln!(
+                f,
+                "{}{4}{}{4}{}{4}{}",
+                id,
+                CsvField(&start, delimiter),
+                CsvField(&end, delimiter),
+                CsvField(&subtitle.text, delimiter),
+                delimiter,
+            )?;
+        }
+
+        Ok(())
+    }
+}
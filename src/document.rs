@@ -0,0 +1,313 @@
+//! A lossless document model for byte-exact round-trips
+//!
+//! [`from_str()`][crate::from_str] throws away anything that doesn't matter for the subtitles
+//! themselves (line endings, extra blank lines, a BOM, how the IDs were formatted, etc.).
+//! [`Document`] holds onto all of that, so rendering it back out leaves every cue that wasn't
+//! touched exactly as it was
+
+use std::{fmt, iter::Peekable};
+
+use crate::{error::Result, ParseOptions, Subtitle};
+
+const BOM: char = '\u{feff}';
+
+/// An SRT document that keeps track of all of the original formatting
+///
+/// ```
+/// use kiss_srt::document::Document;
+///
+/// const TEXT: &str = "\u{feff}001\r\n\
+/// 00:00:00,000 --> 00:00:01,000\r\n\
+/// Leave me be\r\n\
+/// \r\n\
+/// \r\n\
+/// 002\r\n\
+/// 00:00:01,000 --> 00:00:02,000\r\n\
+/// Edit me\r\n";
+///
+/// let mut doc = Document::parse(TEXT).unwrap();
+/// // Untouched documents render exactly the same
+/// assert_eq!(doc.to_string(), TEXT);
+///
+/// // Only the edited part of the cue gets re-rendered
+/// doc.cues_mut()[1].subtitle.text = String::from("Edited");
+/// assert_eq!(doc.to_string(), TEXT.replace("Edit me", "Edited"));
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Document {
+    bom: bool,
+    line_ending: &'static str,
+    cues: Vec<Cue>,
+    // Blank lines and such after the last cue
+    trailing: String,
+}
+
+impl Document {
+    /// Attempts to parse the provided text into a document
+    ///
+    /// This fails in all of the same situations as [`from_str()`][crate::from_str] aside from a
+    /// leading BOM which gets preserved
+    pub fn parse(text: &str) -> Result<Self> {
+        let (bom, text) = if text.starts_with(BOM) {
+            (true, &text[BOM.len_utf8()..])
+        } else {
+            (false, text)
+        };
+        let subtitles = ParseOptions::new()
+            .keep_ids(true)
+            .parse(text)
+            .into_result()?;
+
+        // The text parsed successfully, so the lines can be split up following the same grammar
+        let mut lines = RawLines { rest: text }.peekable();
+        let mut cues = Vec::with_capacity(subtitles.len());
+        for subtitle in subtitles {
+            let leading = collect_lines(&mut lines, is_blank);
+            let id = lines.next().expect("ID line was parsed").to_owned();
+            let ts = lines.next().expect("Timestamp line was parsed").to_owned();
+            let text = collect_lines(&mut lines, |line| !is_blank(line));
+
+            cues.push(Cue {
+                raw: Some(RawCue {
+                    leading,
+                    id,
+                    ts,
+                    text,
+                    original: subtitle.clone(),
+                }),
+                subtitle,
+            });
+        }
+        let trailing = lines.collect();
+
+        let line_ending = match cues.first().and_then(|cue| cue.raw.as_ref()) {
+            Some(raw) if raw.id.ends_with("\r\n") => "\r\n",
+            _ => "\n",
+        };
+
+        Ok(Self {
+            bom,
+            line_ending,
+            cues,
+            trailing,
+        })
+    }
+
+    /// All of the cues in the document
+    pub fn cues(&self) -> &[Cue] {
+        &self.cues
+    }
+
+    /// All of the cues in the document for editing, adding, or removing
+    pub fn cues_mut(&mut self) -> &mut Vec<Cue> {
+        &mut self.cues
+    }
+
+    /// Returns whether the document starts with a BOM
+    pub fn has_bom(&self) -> bool {
+        self.bom
+    }
+
+    /// Sets whether the document starts with a BOM
+    pub fn set_bom(&mut self, bom: bool) {
+        self.bom = bom;
+    }
+
+    /// Clones out all of the subtitles
+    pub fn to_subtitles(&self) -> Vec<Subtitle> {
+        self.cues.iter().map(|cue| cue.subtitle.clone()).collect()
+    }
+}
+
+impl From<Vec<Subtitle>> for Document {
+    fn from(subtitles: Vec<Subtitle>) -> Self {
+        Self {
+            bom: false,
+            line_ending: "\n",
+            cues: subtitles.into_iter().map(Cue::from).collect(),
+            trailing: String::new(),
+        }
+    }
+}
+
+impl fmt::Display for Document {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut out = Output {
+            f,
+            line_ending: self.line_ending,
+            at_line_start: true,
+        };
+
+        if self.bom {
+            out.raw(BOM.encode_utf8(&mut [0; 4]))?;
+        }
+        for (i, cue) in (1..).zip(&self.cues) {
+            let id = cue.subtitle.id.unwrap_or(i);
+            match &cue.raw {
+                Some(raw) => {
+                    let original = &raw.original;
+                    let subtitle = &cue.subtitle;
+                    // Only the first parsed cue has nothing before it, but a new cue can end up
+                    // in front of it
+                    if i != 1 && raw.leading.is_empty() {
+                        out.blank_line()?;
+                    }
+                    out.raw(&raw.leading)?;
+                    if subtitle.id == original.id {
+                        out.raw(&raw.id)?;
+                    } else {
+                        out.line(id)?;
+                    }
+                    if (subtitle.start, subtitle.duration, subtitle.position)
+                        == (original.start, original.duration, original.position)
+                    {
+                        out.raw(&raw.ts)?;
+                    } else {
+                        out.ts_line(subtitle)?;
+                    }
+                    if subtitle.text == original.text {
+                        out.raw(&raw.text)?;
+                    } else {
+                        out.text(&subtitle.text)?;
+                    }
+                }
+                None => {
+                    if i != 1 {
+                        out.blank_line()?;
+                    }
+                    out.line(id)?;
+                    out.ts_line(&cue.subtitle)?;
+                    out.text(&cue.subtitle.text)?;
+                }
+            }
+        }
+        out.raw(&self.trailing)
+    }
+}
+
+/// A single cue within a [`Document`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cue {
+    /// The subtitle which can be freely edited
+    pub subtitle: Subtitle,
+    raw: Option<RawCue>,
+}
+
+impl Cue {
+    /// Creates a new cue that doesn't have any original formatting
+    pub fn new(subtitle: Subtitle) -> Self {
+        Self {
+            subtitle,
+            raw: None,
+        }
+    }
+
+    /// Returns `true` if the cue is new or was changed since being parsed
+    pub fn is_modified(&self) -> bool {
+        self.raw
+            .as_ref()
+            .map_or(true, |raw| raw.original != self.subtitle)
+    }
+}
+
+impl From<Subtitle> for Cue {
+    fn from(subtitle: Subtitle) -> Self {
+        Self::new(subtitle)
+    }
+}
+
+// The original text for each part of a cue including line endings
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct RawCue {
+    leading: String,
+    id: String,
+    ts: String,
+    text: String,
+    original: Subtitle,
+}
+
+// Splits text into lines that keep their line endings
+struct RawLines<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for RawLines<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rest.is_empty() {
+            return None;
+        }
+
+        let len = self.rest.find('\n').map_or(self.rest.len(), |i| i + 1);
+        let (line, rest) = self.rest.split_at(len);
+        self.rest = rest;
+        Some(line)
+    }
+}
+
+// Collects lines up until the first one that doesn't match `keep`
+fn collect_lines(lines: &mut Peekable<RawLines<'_>>, keep: fn(&str) -> bool) -> String {
+    let mut collected = String::new();
+    while let Some(&line) = lines.peek() {
+        if !keep(line) {
+            break;
+        }
+
+        collected.push_str(line);
+        let _ = lines.next();
+    }
+
+    collected
+}
+
+// Lines that end a cue or are skipped between cues
+fn is_blank(line: &str) -> bool {
+    line.trim_end_matches(|c| c == '\r' || c == '\n').is_empty()
+}
+
+// Keeps track of line starts so that new content never gets tacked onto a line missing its ending
+struct Output<'a, 'b> {
+    f: &'a mut fmt::Formatter<'b>,
+    line_ending: &'static str,
+    at_line_start: bool,
+}
+
+impl Output<'_, '_> {
+    fn raw(&mut self, s: &str) -> fmt::Result {
+        if !s.is_empty() {
+            self.at_line_start = s.ends_with('\n');
+        }
+        self.f.write_str(s)
+    }
+
+    fn line(&mut self, line: impl fmt::Display) -> fmt::Result {
+        if !self.at_line_start {
+            self.f.write_str(self.line_ending)?;
+        }
+        self.at_line_start = true;
+        write!(self.f, "{}{}", line, self.line_ending)
+    }
+
+    fn blank_line(&mut self) -> fmt::Result {
+        self.line("")
+    }
+
+    fn ts_line(&mut self, subtitle: &Subtitle) -> fmt::Result {
+        let end = subtitle.start + subtitle.duration;
+        match &subtitle.position {
+            Some(position) => {
+                self.line(format_args!("{} --> {} {}", subtitle.start, end, position))
+            }
+            None => self.line(format_args!("{} --> {}", subtitle.start, end)),
+        }
+    }
+
+    fn text(&mut self, text: &str) -> fmt::Result {
+        for line in text.lines() {
+            self.line(line)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,424 @@
+use std::{borrow::Cow, char, fmt};
+
+use crate::{
+    error::{Error, Result, Span},
+    Subtitle,
+};
+
+mod tables;
+
+const UTF8_BOM: &[u8] = b"\xef\xbb\xbf";
+const UTF16_LE_BOM: &[u8] = b"\xff\xfe";
+const UTF16_BE_BOM: &[u8] = b"\xfe\xff";
+const GB18030_BOM: &[u8] = b"\x84\x31\x95\x33";
+
+// How many leading bytes get checked when sniffing for BOM-less UTF-16
+const UTF16_SNIFF_LEN: usize = 1_024;
+
+// Windows-1252 only differs from Latin-1 in the 0x80..0xA0 range. The holes in the encoding map
+// to their C1 control characters just like the WHATWG spec
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20ac}', '\u{81}', '\u{201a}', '\u{192}', '\u{201e}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{2c6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8d}', '\u{17d}', '\u{8f}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201c}', '\u{201d}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{2dc}', '\u{2122}', '\u{161}', '\u{203a}', '\u{153}', '\u{9d}', '\u{17e}', '\u{178}',
+];
+
+/// The text encodings that can be decoded by [`from_bytes()`] and [`from_bytes_with()`]
+///
+/// The ISO-8859 encodings can't be told apart from each other or from Windows-1252 by looking at
+/// the bytes, so they're never detected. Pass them to [`from_bytes_with()`] instead
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// UTF-8 with or without a BOM
+    Utf8,
+    /// Little-endian UTF-16 with or without a BOM
+    Utf16Le,
+    /// Big-endian UTF-16 with or without a BOM
+    Utf16Be,
+    /// The Windows Western European code page which is a superset of printable Latin-1
+    Windows1252,
+    /// ISO-8859-1 aka Latin-1
+    Latin1,
+    /// ISO-8859-2 aka Latin-2 for Central European languages
+    Iso8859_2,
+    /// ISO-8859-3 aka Latin-3 for South European languages
+    Iso8859_3,
+    /// ISO-8859-4 aka Latin-4 for North European languages
+    Iso8859_4,
+    /// ISO-8859-5 for Cyrillic
+    Iso8859_5,
+    /// ISO-8859-6 for Arabic
+    Iso8859_6,
+    /// ISO-8859-7 for Greek
+    Iso8859_7,
+    /// ISO-8859-8 for Hebrew
+    Iso8859_8,
+    /// ISO-8859-9 aka Latin-5 for Turkish
+    Iso8859_9,
+    /// ISO-8859-10 aka Latin-6 for Nordic languages
+    Iso8859_10,
+    /// ISO-8859-11 for Thai
+    Iso8859_11,
+    /// ISO-8859-13 aka Latin-7 for Baltic languages
+    Iso8859_13,
+    /// ISO-8859-14 aka Latin-8 for Celtic languages
+    Iso8859_14,
+    /// ISO-8859-15 aka Latin-9 which adds the Euro sign to Latin-1
+    Iso8859_15,
+    /// ISO-8859-16 aka Latin-10 for South-Eastern European languages
+    Iso8859_16,
+    /// The Chinese national standard which is a superset of GBK and GB2312
+    Gb18030,
+}
+
+impl Encoding {
+    /// Guesses the encoding of the provided bytes
+    ///
+    /// A BOM always wins. Otherwise BOM-less UTF-16 is sniffed out from the pattern of NUL bytes
+    /// before checking if the text is valid UTF-8, and then if it looks like Chinese text in
+    /// GB18030. Anything else is assumed to be [`Encoding::Windows1252`]
+    ///
+    /// ```
+    /// use kiss_srt::Encoding;
+    ///
+    /// assert_eq!(Encoding::detect(b"\xef\xbb\xbf1\n"), Encoding::Utf8);
+    /// assert_eq!(Encoding::detect(b"1\x00\n\x00"), Encoding::Utf16Le);
+    /// assert_eq!(Encoding::detect("Café".as_bytes()), Encoding::Utf8);
+    /// assert_eq!(Encoding::detect(b"Caf\xe9"), Encoding::Windows1252);
+    /// assert_eq!(Encoding::detect(b"\xc4\xe3\xba\xc3"), Encoding::Gb18030);
+    /// ```
+    pub fn detect(bytes: &[u8]) -> Self {
+        if bytes.starts_with(UTF8_BOM) {
+            Self::Utf8
+        } else if bytes.starts_with(UTF16_LE_BOM) {
+            Self::Utf16Le
+        } else if bytes.starts_with(UTF16_BE_BOM) {
+            Self::Utf16Be
+        } else if bytes.starts_with(GB18030_BOM) {
+            Self::Gb18030
+        } else if let Some(utf16) = sniff_utf16(bytes) {
+            utf16
+        } else if std::str::from_utf8(bytes).is_ok() {
+            Self::Utf8
+        } else if sniff_gb18030(bytes) {
+            Self::Gb18030
+        } else {
+            Self::Windows1252
+        }
+    }
+
+    /// Decodes the `bytes` to text stripping any leading BOM
+    ///
+    /// The single-byte encodings strip a UTF-8 BOM since a forced override on a UTF-8 file would
+    /// otherwise leave it behind as `ï»¿`
+    ///
+    /// This can only fail for the UTF encodings and GB18030. Every byte is valid for the
+    /// single-byte ones, where any bytes that are unassigned in the ISO-8859 encodings become
+    /// U+FFFD
+    ///
+    /// ```
+    /// use kiss_srt::Encoding;
+    ///
+    /// assert_eq!(Encoding::Latin1.decode(b"Caf\xe9").unwrap(), "Café");
+    /// assert_eq!(Encoding::Windows1252.decode(b"\x93Hi\x94").unwrap(), "“Hi”");
+    /// assert_eq!(Encoding::Iso8859_5.decode(b"\xbf\xe0\xd8\xd2\xd5\xe2").unwrap(), "Привет");
+    /// assert_eq!(Encoding::Gb18030.decode(b"\xc4\xe3\xba\xc3").unwrap(), "你好");
+    ///
+    /// let err = Encoding::Utf8.decode(b"1\n\xff").unwrap_err();
+    /// assert_eq!(err.to_string(), "Invalid text encoding on line 2");
+    /// ```
+    pub fn decode(self, bytes: &[u8]) -> Result<Cow<'_, str>> {
+        match self {
+            Self::Utf8 => {
+                let bytes = strip_prefix(bytes, UTF8_BOM);
+                std::str::from_utf8(bytes)
+                    .map(Cow::Borrowed)
+                    .map_err(|err| {
+                        let valid = std::str::from_utf8(&bytes[..err.valid_up_to()])
+                            .expect("Prefix is valid");
+                        invalid_encoding(valid)
+                    })
+            }
+            Self::Utf16Le => decode_utf16(strip_prefix(bytes, UTF16_LE_BOM), u16::from_le_bytes),
+            Self::Utf16Be => decode_utf16(strip_prefix(bytes, UTF16_BE_BOM), u16::from_be_bytes),
+            Self::Windows1252 => Ok(decode_single_byte(
+                strip_prefix(bytes, UTF8_BOM),
+                |b| match b {
+                    0x80..=0x9f => WINDOWS_1252_HIGH[usize::from(b - 0x80)],
+                    _ => char::from(b),
+                },
+            )),
+            Self::Latin1 => Ok(decode_single_byte(
+                strip_prefix(bytes, UTF8_BOM),
+                char::from,
+            )),
+            Self::Iso8859_2 => Ok(decode_iso_8859(bytes, &tables::ISO_8859_2)),
+            Self::Iso8859_3 => Ok(decode_iso_8859(bytes, &tables::ISO_8859_3)),
+            Self::Iso8859_4 => Ok(decode_iso_8859(bytes, &tables::ISO_8859_4)),
+            Self::Iso8859_5 => Ok(decode_iso_8859(bytes, &tables::ISO_8859_5)),
+            Self::Iso8859_6 => Ok(decode_iso_8859(bytes, &tables::ISO_8859_6)),
+            Self::Iso8859_7 => Ok(decode_iso_8859(bytes, &tables::ISO_8859_7)),
+            Self::Iso8859_8 => Ok(decode_iso_8859(bytes, &tables::ISO_8859_8)),
+            Self::Iso8859_9 => Ok(decode_iso_8859(bytes, &tables::ISO_8859_9)),
+            Self::Iso8859_10 => Ok(decode_iso_8859(bytes, &tables::ISO_8859_10)),
+            Self::Iso8859_11 => Ok(decode_iso_8859(bytes, &tables::ISO_8859_11)),
+            Self::Iso8859_13 => Ok(decode_iso_8859(bytes, &tables::ISO_8859_13)),
+            Self::Iso8859_14 => Ok(decode_iso_8859(bytes, &tables::ISO_8859_14)),
+            Self::Iso8859_15 => Ok(decode_iso_8859(bytes, &tables::ISO_8859_15)),
+            Self::Iso8859_16 => Ok(decode_iso_8859(bytes, &tables::ISO_8859_16)),
+            Self::Gb18030 => decode_gb18030(strip_prefix(bytes, UTF8_BOM)),
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Utf8 => "UTF-8",
+            Self::Utf16Le => "UTF-16LE",
+            Self::Utf16Be => "UTF-16BE",
+            Self::Windows1252 => "windows-1252",
+            Self::Latin1 => "ISO-8859-1",
+            Self::Iso8859_2 => "ISO-8859-2",
+            Self::Iso8859_3 => "ISO-8859-3",
+            Self::Iso8859_4 => "ISO-8859-4",
+            Self::Iso8859_5 => "ISO-8859-5",
+            Self::Iso8859_6 => "ISO-8859-6",
+            Self::Iso8859_7 => "ISO-8859-7",
+            Self::Iso8859_8 => "ISO-8859-8",
+            Self::Iso8859_9 => "ISO-8859-9",
+            Self::Iso8859_10 => "ISO-8859-10",
+            Self::Iso8859_11 => "ISO-8859-11",
+            Self::Iso8859_13 => "ISO-8859-13",
+            Self::Iso8859_14 => "ISO-8859-14",
+            Self::Iso8859_15 => "ISO-8859-15",
+            Self::Iso8859_16 => "ISO-8859-16",
+            Self::Gb18030 => "GB18030",
+        })
+    }
+}
+
+/// Attempts to parse the provided bytes to a [`Vec`] of [`Subtitle`]s after detecting the encoding
+///
+/// The encoding gets guessed with [`Encoding::detect()`] and is returned alongside the
+/// subtitles. Use [`from_bytes_with()`] to override the guess
+///
+/// ```
+/// use kiss_srt::Encoding;
+///
+/// // UTF-16LE with a BOM
+/// let bytes: Vec<u8> = "\u{feff}1\n00:00:00,000 --> 00:00:01,000\nHi\n"
+///     .encode_utf16()
+///     .flat_map(|unit| unit.to_le_bytes().to_vec())
+///     .collect();
+///
+/// let (subtitles, encoding) = kiss_srt::from_bytes(&bytes).unwrap();
+/// assert_eq!(encoding, Encoding::Utf16Le);
+/// assert_eq!(subtitles[0].text, "Hi");
+/// ```
+pub fn from_bytes(bytes: &[u8]) -> Result<(Vec<Subtitle>, Encoding)> {
+    let encoding = Encoding::detect(bytes);
+    let text = encoding.decode(bytes)?;
+    let subtitles = crate::from_str(&text)?;
+
+    Ok((subtitles, encoding))
+}
+
+/// Attempts to parse the provided bytes to a [`Vec`] of [`Subtitle`]s using the given `encoding`
+/// instead of detecting it
+///
+/// ```
+/// use kiss_srt::Encoding;
+///
+/// const BYTES: &[u8] = b"1\n00:00:00,000 --> 00:00:01,000\n\xa4 5\n";
+///
+/// // Windows-1252 gets detected, but this is really ISO-8859-15 where 0xA4 is the Euro sign
+/// assert_eq!(kiss_srt::from_bytes(BYTES).unwrap().0[0].text, "¤ 5");
+/// let subtitles = kiss_srt::from_bytes_with(BYTES, Encoding::Iso8859_15).unwrap();
+/// assert_eq!(subtitles[0].text, "€ 5");
+/// ```
+pub fn from_bytes_with(bytes: &[u8], encoding: Encoding) -> Result<Vec<Subtitle>> {
+    let text = encoding.decode(bytes)?;
+    crate::from_str(&text)
+}
+
+fn strip_prefix<'a>(bytes: &'a [u8], prefix: &[u8]) -> &'a [u8] {
+    if bytes.starts_with(prefix) {
+        &bytes[prefix.len()..]
+    } else {
+        bytes
+    }
+}
+
+// SRT text is mostly ASCII, so UTF-16 winds up with NUL bytes on one side of most code units.
+// Plenty of characters have a NUL on the other side too (like `一` which is U+4E00), so it only
+// takes that side having far fewer NULs instead of none at all
+fn sniff_utf16(bytes: &[u8]) -> Option<Encoding> {
+    let sample = &bytes[..std::cmp::min(bytes.len(), UTF16_SNIFF_LEN)];
+    let (mut even_nuls, mut odd_nuls) = (0, 0);
+    for pair in sample.chunks_exact(2) {
+        even_nuls += usize::from(pair[0] == 0);
+        odd_nuls += usize::from(pair[1] == 0);
+    }
+
+    let num_pairs = sample.len() / 2;
+    let dominates = |nuls: usize, other_nuls: usize| nuls * 4 > num_pairs && other_nuls * 4 < nuls;
+    if dominates(odd_nuls, even_nuls) {
+        Some(Encoding::Utf16Le)
+    } else if dominates(even_nuls, odd_nuls) {
+        Some(Encoding::Utf16Be)
+    } else {
+        None
+    }
+}
+
+// Legacy Chinese text is almost all two-byte codes with both bytes in the high half (or the odd
+// four-byte code), whereas Windows-1252 text mostly has lone high bytes between the ASCII
+fn sniff_gb18030(bytes: &[u8]) -> bool {
+    let (mut high_pairs, mut lone_highs) = (0, 0);
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] < 0x80 {
+            i += 1;
+        } else if bytes.get(i + 1).map_or(false, |&trail| trail >= 0x80) {
+            high_pairs += 1;
+            i += 2;
+        } else if is_gb18030_four_byte(&bytes[i..]) {
+            high_pairs += 1;
+            i += 4;
+        } else {
+            lone_highs += 1;
+            i += 1;
+        }
+    }
+
+    high_pairs > lone_highs * 4 && Encoding::Gb18030.decode(bytes).is_ok()
+}
+
+fn is_gb18030_four_byte(bytes: &[u8]) -> bool {
+    let ranges = [0x81..=0xfe, 0x30..=0x39, 0x81..=0xfe, 0x30..=0x39];
+    bytes.len() >= 4
+        && bytes
+            .iter()
+            .zip(ranges.iter())
+            .all(|(b, range)| range.contains(b))
+}
+
+fn decode_utf16(bytes: &[u8], to_unit: fn([u8; 2]) -> u16) -> Result<Cow<'_, str>> {
+    let units = bytes
+        .chunks_exact(2)
+        .map(|pair| to_unit([pair[0], pair[1]]));
+    let mut text = String::with_capacity(bytes.len() / 2);
+    for c in char::decode_utf16(units) {
+        match c {
+            Ok(c) => text.push(c),
+            Err(_) => return Err(invalid_encoding(&text)),
+        }
+    }
+
+    // A dangling byte can't make up a full code unit
+    if bytes.len() % 2 == 1 {
+        return Err(invalid_encoding(&text));
+    }
+
+    Ok(Cow::Owned(text))
+}
+
+fn decode_single_byte(bytes: &[u8], to_char: impl Fn(u8) -> char) -> Cow<'_, str> {
+    if bytes.is_ascii() {
+        Cow::Borrowed(std::str::from_utf8(bytes).expect("ASCII is valid UTF-8"))
+    } else {
+        Cow::Owned(bytes.iter().copied().map(to_char).collect())
+    }
+}
+
+// The bytes below 0xA0 are the same as Latin-1 for the whole ISO-8859 family
+fn decode_iso_8859<'a>(bytes: &'a [u8], high: &[char; 96]) -> Cow<'a, str> {
+    decode_single_byte(strip_prefix(bytes, UTF8_BOM), |b| match b {
+        0xa0..=0xff => high[usize::from(b - 0xa0)],
+        _ => char::from(b),
+    })
+}
+
+// GB18030 is one byte for ASCII, two bytes for most of the BMP, and four bytes for everything else
+fn decode_gb18030(bytes: &[u8]) -> Result<Cow<'_, str>> {
+    if bytes.is_ascii() {
+        return Ok(Cow::Borrowed(
+            std::str::from_utf8(bytes).expect("ASCII is valid UTF-8"),
+        ));
+    }
+
+    let mut text = String::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let lead = bytes[i];
+        let (c, len) = match (lead, bytes.get(i + 1).copied()) {
+            (0x00..=0x7f, _) => (Some(char::from(lead)), 1),
+            (0x80, _) => (Some('\u{20ac}'), 1),
+            (0x81..=0xfe, Some(second @ 0x30..=0x39)) => {
+                let rest = (bytes.get(i + 2).copied(), bytes.get(i + 3).copied());
+                let c = match rest {
+                    (Some(third @ 0x81..=0xfe), Some(fourth @ 0x30..=0x39)) => {
+                        gb18030_four_byte([lead, second, third, fourth])
+                    }
+                    _ => None,
+                };
+                (c, 4)
+            }
+            (0x81..=0xfe, Some(trail @ 0x40..=0x7e)) | (0x81..=0xfe, Some(trail @ 0x80..=0xfe)) => {
+                let offset = if trail < 0x7f { 0x40 } else { 0x41 };
+                let index = usize::from(lead - 0x81) * 190 + usize::from(trail - offset);
+                (
+                    char::from_u32(u32::from(tables::GB18030_TWO_BYTE[index])),
+                    2,
+                )
+            }
+            _ => (None, 1),
+        };
+
+        match c {
+            Some(c) => text.push(c),
+            None => return Err(invalid_encoding(&text)),
+        }
+        i += len;
+    }
+
+    // A GB18030 BOM decodes to U+FEFF just like the UTF ones
+    if text.starts_with('\u{feff}') {
+        text.remove(0);
+    }
+
+    Ok(Cow::Owned(text))
+}
+
+fn gb18030_four_byte(bytes: [u8; 4]) -> Option<char> {
+    let [first, second, third, fourth] = bytes;
+    let pointer = ((u32::from(first - 0x81) * 10 + u32::from(second - 0x30)) * 1260)
+        + u32::from(third - 0x81) * 10
+        + u32::from(fourth - 0x30);
+
+    match pointer {
+        // Everything outside of the BMP is one continuous run
+        189_000..=1_237_575 => char::from_u32(0x10000 + pointer - 189_000),
+        0..=39_419 => {
+            let run =
+                match tables::GB18030_RANGES.binary_search_by_key(&pointer, |&(start, _)| start) {
+                    Ok(run) => run,
+                    // The first run starts at zero, so this can't underflow
+                    Err(run) => run - 1,
+                };
+            let (start, code_point) = tables::GB18030_RANGES[run];
+            char::from_u32(u32::from(code_point) + pointer - start)
+        }
+        _ => None,
+    }
+}
+
+// Points at the spot right after all of the text that was successfully decoded
+fn invalid_encoding(decoded: &str) -> Error {
+    let line = decoded.matches('\n').count() + 1;
+    let column = decoded.len() - decoded.rfind('\n').map_or(0, |i| i + 1);
+
+    Error::invalid_encoding(line, Span::new(column, column))
+}
@@ -1,6 +1,6 @@
 //! All the possible errors and the context that comes with them
 
-use std::fmt;
+use std::{cmp, fmt, io, ops::Range};
 
 /// A specialized [`Result`][std::result::Result] for [`kiss_srt::Error`][Error]
 pub type Result<T> = std::result::Result<T, Error>;
@@ -10,50 +10,425 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub struct Error {
     /// The line number that failed parsing
     pub line: usize,
+    /// The bytes within the line that caused the failure
+    ///
+    /// This is empty when there's nothing to point at, like when the line is missing entirely
+    pub span: Span,
+    /// The part of the timestamp that failed parsing for the timestamp [`ErrorKind`]s
+    pub part: Option<TimestampPart>,
     /// The kind of failure
     pub kind: ErrorKind,
 }
 
 impl Error {
-    pub(crate) fn invalid_id(line: usize) -> Self {
+    fn new(line: usize, span: Span, kind: ErrorKind) -> Self {
         Self {
             line,
-            kind: ErrorKind::InvalidId,
+            span,
+            part: None,
+            kind,
         }
     }
 
-    pub(crate) fn invalid_ts_line(line: usize) -> Self {
+    pub(crate) fn invalid_id(line: usize, span: Span) -> Self {
+        Self::new(line, span, ErrorKind::InvalidId)
+    }
+
+    pub(crate) fn invalid_ts_line(line: usize, span: Span) -> Self {
+        Self::new(line, span, ErrorKind::InvalidTimestampLine)
+    }
+
+    pub(crate) fn invalid_ts_start(line: usize, span: Span, part: TimestampPart) -> Self {
         Self {
-            line,
-            kind: ErrorKind::InvalidTimestampLine,
+            part: Some(part),
+            ..Self::new(line, span, ErrorKind::InvalidTimestampStart)
         }
     }
 
-    pub(crate) fn invalid_ts_start(line: usize) -> Self {
+    pub(crate) fn invalid_ts_divider(line: usize, span: Span) -> Self {
+        Self::new(line, span, ErrorKind::InvalidTimestampDivider)
+    }
+
+    pub(crate) fn invalid_ts_end(line: usize, span: Span, part: TimestampPart) -> Self {
         Self {
-            line,
-            kind: ErrorKind::InvalidTimestampStart,
+            part: Some(part),
+            ..Self::new(line, span, ErrorKind::InvalidTimestampEnd)
         }
     }
 
-    pub(crate) fn invalid_ts_divider(line: usize) -> Self {
+    pub(crate) fn ts_end_before_start(line: usize, span: Span) -> Self {
+        Self::new(line, span, ErrorKind::TimestampEndBeforeStart)
+    }
+
+    pub(crate) fn invalid_encoding(line: usize, span: Span) -> Self {
+        Self::new(line, span, ErrorKind::InvalidEncoding)
+    }
+
+    pub(crate) fn invalid_vtt_header(line: usize, span: Span) -> Self {
+        Self::new(line, span, ErrorKind::InvalidWebVttHeader)
+    }
+
+    pub(crate) fn missing_cue_timing(line: usize, span: Span) -> Self {
+        Self::new(line, span, ErrorKind::MissingCueTiming)
+    }
+
+    pub(crate) fn invalid_frame_number(line: usize, span: Span) -> Self {
+        Self::new(line, span, ErrorKind::InvalidFrameNumber)
+    }
+
+    pub(crate) fn missing_ass_events(line: usize) -> Self {
+        Self::new(line, Span::default(), ErrorKind::MissingAssEvents)
+    }
+
+    pub(crate) fn invalid_ass_format(line: usize, span: Span) -> Self {
+        Self::new(line, span, ErrorKind::InvalidAssFormat)
+    }
+
+    pub(crate) fn invalid_ass_dialogue(line: usize, span: Span) -> Self {
+        Self::new(line, span, ErrorKind::InvalidAssDialogue)
+    }
+
+    pub(crate) fn invalid_ass_ts(line: usize, span: Span, part: TimestampPart) -> Self {
         Self {
-            line,
-            kind: ErrorKind::InvalidTimestampDivider,
+            part: Some(part),
+            ..Self::new(line, span, ErrorKind::InvalidAssTimestamp)
         }
     }
 
-    pub(crate) fn invalid_ts_end(line: usize) -> Self {
+    pub(crate) fn invalid_sbv_ts(line: usize, span: Span, part: TimestampPart) -> Self {
         Self {
-            line,
-            kind: ErrorKind::InvalidTimestampEnd,
+            part: Some(part),
+            ..Self::new(line, span, ErrorKind::InvalidSbvTimestamp)
         }
     }
 
-    pub(crate) fn ts_end_before_start(line: usize) -> Self {
+    pub(crate) fn invalid_subviewer_ts(line: usize, span: Span, part: TimestampPart) -> Self {
         Self {
-            line,
-            kind: ErrorKind::TimestampEndBeforeStart,
+            part: Some(part),
+            ..Self::new(line, span, ErrorKind::InvalidSubViewerTimestamp)
+        }
+    }
+
+    pub(crate) fn missing_ts_comma(line: usize, span: Span) -> Self {
+        Self::new(line, span, ErrorKind::MissingTimestampComma)
+    }
+
+    pub(crate) fn missing_sami_tag(line: usize, span: Span) -> Self {
+        Self::new(line, span, ErrorKind::MissingSamiTag)
+    }
+
+    pub(crate) fn invalid_sync_start(line: usize, span: Span) -> Self {
+        Self::new(line, span, ErrorKind::InvalidSyncStart)
+    }
+
+    pub(crate) fn invalid_xml(line: usize, span: Span) -> Self {
+        Self::new(line, span, ErrorKind::InvalidXml)
+    }
+
+    pub(crate) fn invalid_ttml_root(line: usize, span: Span) -> Self {
+        Self::new(line, span, ErrorKind::InvalidTtmlRoot)
+    }
+
+    pub(crate) fn missing_ttml_timing(line: usize, span: Span) -> Self {
+        Self::new(line, span, ErrorKind::MissingTtmlTiming)
+    }
+
+    pub(crate) fn invalid_ttml_time(line: usize, span: Span) -> Self {
+        Self::new(line, span, ErrorKind::InvalidTtmlTime)
+    }
+
+    pub(crate) fn missing_csv_column(line: usize, span: Span) -> Self {
+        Self::new(line, span, ErrorKind::MissingCsvColumn)
+    }
+
+    pub(crate) fn missing_csv_field(line: usize, span: Span) -> Self {
+        Self::new(line, span, ErrorKind::MissingCsvField)
+    }
+
+    pub(crate) fn unclosed_csv_quote(line: usize, span: Span) -> Self {
+        Self::new(line, span, ErrorKind::UnclosedCsvQuote)
+    }
+
+    pub(crate) fn invalid_csv_quote(line: usize, span: Span) -> Self {
+        Self::new(line, span, ErrorKind::InvalidCsvQuote)
+    }
+
+    pub(crate) fn invalid_numeric_ts(line: usize, span: Span, part: TimestampPart) -> Self {
+        Self {
+            part: Some(part),
+            ..Self::new(line, span, ErrorKind::InvalidNumericTimestamp)
+        }
+    }
+
+    pub(crate) fn invalid_json(line: usize, span: Span) -> Self {
+        Self::new(line, span, ErrorKind::InvalidJson)
+    }
+
+    pub(crate) fn invalid_json_subtitle(line: usize, span: Span) -> Self {
+        Self::new(line, span, ErrorKind::InvalidJsonSubtitle)
+    }
+
+    pub(crate) fn missing_json_key(line: usize, span: Span) -> Self {
+        Self::new(line, span, ErrorKind::MissingJsonKey)
+    }
+
+    pub(crate) fn invalid_json_ts(line: usize, span: Span) -> Self {
+        Self {
+            part: Some(TimestampPart::Millis),
+            ..Self::new(line, span, ErrorKind::InvalidJsonTimestamp)
+        }
+    }
+
+    pub(crate) fn invalid_vtt_ts(line: usize, span: Span, part: TimestampPart) -> Self {
+        Self {
+            part: Some(part),
+            ..Self::new(line, span, ErrorKind::InvalidWebVttTimestamp)
+        }
+    }
+
+    /// The 1-based column of the start of the [`span`][Self::span] in bytes
+    pub fn column(&self) -> usize {
+        self.span.start + 1
+    }
+
+    /// Pairs the error with the `source` text it came from to render it like a compiler
+    /// diagnostic
+    ///
+    /// ```
+    /// const TEXT: &str = "\
+    /// 1
+    /// 00:00:00.000 --> 00:00:01,000
+    /// Uses a '.' instead of a ','
+    /// ";
+    ///
+    /// let err = kiss_srt::from_str(TEXT).unwrap_err();
+    /// assert_eq!(
+    ///     err.annotate(TEXT).to_string(),
+    ///     "\
+    /// error: Invalid starting timestamp
+    ///  --> line 2, column 9
+    ///   |
+    /// 2 | 00:00:00.000 --> 00:00:01,000
+    ///   |         ^ expected `,` found `.`
+    ///   |
+    ///   = help: replace `.` with `,`",
+    /// );
+    /// ```
+    pub fn annotate<'a>(&self, source: &'a str) -> Annotated<'a> {
+        Annotated {
+            error: *self,
+            source,
+        }
+    }
+
+    // A label for what's under the span along with a suggested fix
+    fn hint(&self, found: &str) -> (String, Option<String>) {
+        let found_desc = if found.is_empty() {
+            String::from("the end of the line")
+        } else {
+            format!("`{}`", found)
+        };
+
+        match self.kind {
+            ErrorKind::InvalidId => (
+                String::from("expected only ASCII digits"),
+                Some(String::from("IDs are plain numbers like `1`")),
+            ),
+            ErrorKind::InvalidTimestampLine if self.span.is_empty() => (
+                String::from("expected a timestamp line"),
+                Some(String::from(
+                    "add a timestamp line like `00:00:01,000 --> 00:00:02,000`",
+                )),
+            ),
+            ErrorKind::InvalidTimestampLine => (
+                String::from("unexpected trailing bytes"),
+                Some(String::from("remove everything after the ending timestamp")),
+            ),
+            ErrorKind::InvalidTimestampStart
+            | ErrorKind::InvalidTimestampEnd
+            | ErrorKind::InvalidWebVttTimestamp
+            | ErrorKind::InvalidAssTimestamp
+            | ErrorKind::InvalidSbvTimestamp
+            | ErrorKind::InvalidSubViewerTimestamp => {
+                let is_srt = self.kind == ErrorKind::InvalidTimestampStart
+                    || self.kind == ErrorKind::InvalidTimestampEnd;
+                let unpadded_hours = self.kind == ErrorKind::InvalidAssTimestamp
+                    || self.kind == ErrorKind::InvalidSbvTimestamp;
+                let is_centis = self.kind == ErrorKind::InvalidAssTimestamp
+                    || self.kind == ErrorKind::InvalidSubViewerTimestamp;
+                let (expected, fix) = match self.part {
+                    Some(TimestampPart::HoursSeparator) | Some(TimestampPart::MinutesSeparator) => {
+                        ("`:`", None)
+                    }
+                    Some(TimestampPart::SecondsSeparator) if !is_srt => ("`.`", None),
+                    Some(TimestampPart::SecondsSeparator) => ("`,`", None),
+                    Some(TimestampPart::Hours) if unpadded_hours => {
+                        ("one or two digits", Some("from `0` to `99`"))
+                    }
+                    Some(TimestampPart::Hours) => ("two digits", Some("from `00` to `99`")),
+                    Some(TimestampPart::Minutes) | Some(TimestampPart::Seconds) => {
+                        ("two digits", Some("from `00` to `59`"))
+                    }
+                    Some(TimestampPart::Millis) if is_centis => {
+                        ("two digits", Some("from `00` to `99`"))
+                    }
+                    Some(TimestampPart::Millis) => ("three digits", Some("from `000` to `999`")),
+                    None => ("a timestamp", None),
+                };
+                let help = match fix {
+                    Some(range) => format!("use {} {}", expected, range),
+                    None if found.is_empty() => format!("insert {}", expected),
+                    None => format!("replace {} with {}", found_desc, expected),
+                };
+
+                (
+                    format!("expected {} found {}", expected, found_desc),
+                    Some(help),
+                )
+            }
+            ErrorKind::InvalidTimestampDivider => (
+                format!("expected ` --> ` found {}", found_desc),
+                Some(String::from("separate the timestamps with ` --> `")),
+            ),
+            ErrorKind::MissingTimestampComma => (
+                format!("expected `,` found {}", found_desc),
+                Some(String::from("separate the timestamps with `,`")),
+            ),
+            ErrorKind::TimestampEndBeforeStart => (
+                String::from("this is before the starting timestamp"),
+                Some(String::from(
+                    "the ending timestamp must be at or after the start",
+                )),
+            ),
+            ErrorKind::InvalidEncoding => (
+                String::from("the text can't be decoded after this"),
+                Some(String::from("double check the encoding of the file")),
+            ),
+            ErrorKind::InvalidWebVttHeader => (
+                String::from("expected `WEBVTT`"),
+                Some(String::from("start the file with a `WEBVTT` line")),
+            ),
+            ErrorKind::MissingAssEvents => (
+                String::from("expected an `[Events]` section"),
+                Some(String::from(
+                    "add an `[Events]` section with a `Format:` line and `Dialogue:` lines",
+                )),
+            ),
+            ErrorKind::InvalidAssFormat => (
+                String::from("expected `Start`, `End`, and `Text` fields"),
+                Some(String::from(
+                    "use `Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, \
+                     Effect, Text`",
+                )),
+            ),
+            ErrorKind::InvalidAssDialogue => (
+                String::from("missing fields"),
+                Some(String::from(
+                    "include a value for every field in the `Format:` line",
+                )),
+            ),
+            ErrorKind::InvalidFrameNumber => (
+                String::from("expected a frame number in braces"),
+                Some(String::from("start the line with frames like `{25}{75}`")),
+            ),
+            ErrorKind::MissingCueTiming => (
+                String::from("expected a timing line"),
+                Some(String::from(
+                    "add a timing line like `00:01.000 --> 00:02.000`",
+                )),
+            ),
+            ErrorKind::MissingSamiTag => (
+                String::from("expected a `<SAMI>` tag"),
+                Some(String::from("start the file with `<SAMI>`")),
+            ),
+            ErrorKind::InvalidSyncStart => (
+                String::from("expected a start time in milliseconds"),
+                Some(String::from("use a start time like `<SYNC Start=1000>`")),
+            ),
+            ErrorKind::InvalidXml => (
+                String::from("the XML isn't well-formed here"),
+                Some(String::from(
+                    "make sure every element is closed and every attribute value is quoted",
+                )),
+            ),
+            ErrorKind::InvalidTtmlRoot => (
+                String::from("expected a `<tt>` element"),
+                Some(String::from(
+                    "wrap the document in `<tt xmlns=\"http://www.w3.org/ns/ttml\">`",
+                )),
+            ),
+            ErrorKind::MissingTtmlTiming => (
+                String::from("expected an `end` or `dur` attribute"),
+                Some(String::from(
+                    "add timing like `begin=\"00:00:01.000\" end=\"00:00:02.000\"`",
+                )),
+            ),
+            ErrorKind::InvalidTtmlTime => (
+                format!("expected a time expression found {}", found_desc),
+                Some(String::from(
+                    "use a clock time like `00:00:01.500` or an offset like `1.5s`",
+                )),
+            ),
+            ErrorKind::MissingCsvColumn => (
+                String::from("expected `start`, `end`, and `text` columns"),
+                Some(String::from(
+                    "start the file with a header row like `id,start,end,text`",
+                )),
+            ),
+            ErrorKind::MissingCsvField => (
+                String::from("expected more fields"),
+                Some(String::from(
+                    "include a value for every column in the header row",
+                )),
+            ),
+            ErrorKind::UnclosedCsvQuote => (
+                String::from("this quote is never closed"),
+                Some(String::from("add the closing `\"` after the field")),
+            ),
+            ErrorKind::InvalidCsvQuote => (
+                format!(
+                    "expected a delimiter or the end of the row found {}",
+                    found_desc
+                ),
+                Some(String::from(
+                    "escape quotes within a quoted field by doubling them like `\"\"`",
+                )),
+            ),
+            ErrorKind::InvalidNumericTimestamp if self.part == Some(TimestampPart::Seconds) => (
+                format!("expected a number of seconds found {}", found_desc),
+                Some(String::from("use a number like `1.5`")),
+            ),
+            ErrorKind::InvalidNumericTimestamp => (
+                format!("expected a number of milliseconds found {}", found_desc),
+                Some(String::from("use a whole number like `1500`")),
+            ),
+            ErrorKind::InvalidJson => (
+                if found.is_empty() {
+                    String::from("unexpected end of the line")
+                } else {
+                    format!("unexpected {}", found_desc)
+                },
+                Some(String::from(
+                    "the subtitles should be an array like \
+                     `[{\"start\": 1000, \"end\": 2000, \"text\": \"Hello\"}]`",
+                )),
+            ),
+            ErrorKind::MissingJsonKey => (
+                String::from("expected `start`, `end`, and `text` keys"),
+                Some(String::from(
+                    "subtitles look like `{\"start\": 1000, \"end\": 2000, \"text\": \"Hello\"}`",
+                )),
+            ),
+            ErrorKind::InvalidJsonSubtitle => (
+                String::from("this value has the wrong type"),
+                Some(String::from(
+                    "subtitles look like `{\"start\": 1000, \"end\": 2000, \"text\": \"Hello\"}`",
+                )),
+            ),
+            ErrorKind::InvalidJsonTimestamp => (
+                format!("expected a number of milliseconds found {}", found_desc),
+                Some(String::from("use a whole number like `1500`")),
+            ),
         }
     }
 }
@@ -66,6 +441,212 @@ impl fmt::Display for Error {
 
 impl std::error::Error for Error {}
 
+/// Contains context on why reading subtitles with a [`Reader`][crate::Reader] failed
+#[derive(Debug)]
+pub enum ReadError {
+    /// Reading from the underlying reader failed
+    Io(io::Error),
+    /// The subtitles failed parsing
+    Parse(Error),
+}
+
+impl From<io::Error> for ReadError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+impl From<Error> for ReadError {
+    fn from(err: Error) -> Self {
+        Self::Parse(err)
+    }
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(err) => write!(f, "Failed reading subtitles: {}", err),
+            Self::Parse(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for ReadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            Self::Parse(err) => Some(err),
+        }
+    }
+}
+
+/// A subtitle that would render to invalid SRT
+///
+/// Returned from [`try_to_string()`][crate::try_to_string] when a subtitle's `text` contains an
+/// empty line, which would otherwise end the cue early
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RenderError {
+    /// The index of the offending subtitle
+    pub index: usize,
+    /// The line number of the first empty line within the subtitle's `text`
+    pub text_line: usize,
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Empty line {} in the text of the subtitle at index {}",
+            self.text_line, self.index
+        )
+    }
+}
+
+impl std::error::Error for RenderError {}
+
+/// Text that can't be used as a [`SubtitleText`][crate::SubtitleText]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TextError {
+    /// The line number of the first empty line within the text
+    pub text_line: usize,
+}
+
+impl fmt::Display for TextError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Empty line {} in the text", self.text_line)
+    }
+}
+
+impl std::error::Error for TextError {}
+
+/// An [`Error`] rendered along with the offending line of input
+///
+/// Created with [`Error::annotate()`]
+#[derive(Clone, Copy, Debug)]
+pub struct Annotated<'a> {
+    error: Error,
+    source: &'a str,
+}
+
+impl fmt::Display for Annotated<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Error { line, span, .. } = self.error;
+        let text = self
+            .source
+            .lines()
+            .nth(line.saturating_sub(1))
+            .unwrap_or_default()
+            .trim_end_matches('\r');
+        // The span may not line up if the error came from some other text
+        let (before, found) = match (text.get(..span.start), text.get(span.range())) {
+            (Some(before), Some(found)) => (before, found),
+            _ => (text, ""),
+        };
+        let (label, help) = self.error.hint(found);
+
+        let gutter = line.to_string();
+        let pad = " ".repeat(gutter.len());
+        writeln!(f, "error: {}", self.error.kind)?;
+        writeln!(
+            f,
+            "{}--> line {}, column {}",
+            pad,
+            line,
+            self.error.column()
+        )?;
+        writeln!(f, "{} |", pad)?;
+        writeln!(f, "{} | {}", gutter, text)?;
+        write!(
+            f,
+            "{} | {}{} {}",
+            pad,
+            " ".repeat(before.chars().count()),
+            "^".repeat(cmp::max(found.chars().count(), 1)),
+            label,
+        )?;
+        if let Some(help) = help {
+            write!(f, "\n{} |\n{} = help: {}", pad, pad, help)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A range of bytes within a single line
+///
+/// ```
+/// # use kiss_srt::error::{Span, TimestampPart};
+/// const TEXT: &str = "\
+/// 1
+/// 00:00:00.000 --> 00:00:01,000
+/// Uses a '.' instead of a ','
+/// ";
+///
+/// let err = kiss_srt::from_str(TEXT).unwrap_err();
+/// assert_eq!(err.line, 2);
+/// assert_eq!(err.span, Span::new(8, 9));
+/// assert_eq!(err.part, Some(TimestampPart::SecondsSeparator));
+/// let line = TEXT.lines().nth(err.line - 1).unwrap();
+/// assert_eq!(&line[err.span.range()], ".");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Span {
+    /// The byte offset where the span starts
+    pub start: usize,
+    /// The byte offset where the span ends (exclusive)
+    pub end: usize,
+}
+
+impl Span {
+    /// Constructs a span covering `start..end`
+    pub fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    /// The span as a [`Range`] for easy slicing
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+
+    /// Returns the length in bytes
+    pub fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    /// Returns `true` when the span doesn't cover anything
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+impl From<Span> for Range<usize> {
+    fn from(span: Span) -> Self {
+        span.range()
+    }
+}
+
+/// The different parts of a timestamp in the order they appear
+///
+/// Separators are named after the component they follow, so for `01:23:45,678`
+/// [`SecondsSeparator`][Self::SecondsSeparator] is the `,`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TimestampPart {
+    /// The `01` in `01:23:45,678`
+    Hours,
+    /// The first `:` in `01:23:45,678`
+    HoursSeparator,
+    /// The `23` in `01:23:45,678`
+    Minutes,
+    /// The second `:` in `01:23:45,678`
+    MinutesSeparator,
+    /// The `45` in `01:23:45,678`
+    Seconds,
+    /// The `,` in `01:23:45,678`
+    SecondsSeparator,
+    /// The `678` in `01:23:45,678`
+    Millis,
+}
+
 /// Describes the kind of failure
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ErrorKind {
@@ -81,6 +662,72 @@ pub enum ErrorKind {
     InvalidTimestampEnd,
     /// The ending timestamp is before the start
     TimestampEndBeforeStart,
+    /// The text isn't valid for its encoding
+    InvalidEncoding,
+    /// The WebVTT file doesn't start with a `WEBVTT` line
+    InvalidWebVttHeader,
+    /// The WebVTT cue doesn't have a timing line after its identifier
+    MissingCueTiming,
+    /// A WebVTT timestamp doesn't match the format of `01:23:45.678` or `23:45.678`
+    InvalidWebVttTimestamp,
+    /// The ASS/SSA script doesn't have an `[Events]` section
+    MissingAssEvents,
+    /// The ASS/SSA `Format:` line is missing a `Start`, `End`, or `Text` field
+    InvalidAssFormat,
+    /// The ASS/SSA `Dialogue:` line has fewer fields than its `Format:` line
+    InvalidAssDialogue,
+    /// An ASS/SSA timestamp doesn't match the format of `1:23:45.67`
+    ///
+    /// The centiseconds get reported as [`TimestampPart::Millis`]
+    InvalidAssTimestamp,
+    /// The MicroDVD line doesn't start with frame numbers like `{25}{75}`
+    InvalidFrameNumber,
+    /// An SBV timestamp doesn't match the format of `1:23:45.678`
+    InvalidSbvTimestamp,
+    /// A SubViewer timestamp doesn't match the format of `01:23:45.67`
+    ///
+    /// The centiseconds get reported as [`TimestampPart::Millis`]
+    InvalidSubViewerTimestamp,
+    /// The SBV or SubViewer timestamps aren't separated by a `,`
+    MissingTimestampComma,
+    /// The SAMI file doesn't have a `<SAMI>` tag
+    MissingSamiTag,
+    /// A SAMI `<SYNC>` tag's `Start` is missing or isn't a number of milliseconds
+    InvalidSyncStart,
+    /// The TTML document isn't well-formed XML
+    InvalidXml,
+    /// The TTML document's root element isn't `<tt>`
+    InvalidTtmlRoot,
+    /// A TTML `<p>` doesn't have an `end` or `dur` attribute
+    MissingTtmlTiming,
+    /// A TTML time expression doesn't match a clock time like `01:23:45.678` or an offset like
+    /// `1.5s`
+    InvalidTtmlTime,
+    /// The CSV header row is missing a `start`, `end`, or `text` column
+    MissingCsvColumn,
+    /// A CSV row has fewer fields than it needs for the `start`, `end`, and `text` columns
+    MissingCsvField,
+    /// A quoted CSV field is never closed
+    UnclosedCsvQuote,
+    /// A quoted CSV field has something other than a delimiter or the end of the row after its
+    /// closing quote
+    InvalidCsvQuote,
+    /// A CSV timestamp isn't a number of milliseconds like `1500` or seconds like `1.5`
+    ///
+    /// The [`TimestampPart`] is [`Millis`][TimestampPart::Millis] or
+    /// [`Seconds`][TimestampPart::Seconds] depending on which was expected
+    InvalidNumericTimestamp,
+    /// The JSON isn't valid JSON or isn't an array
+    InvalidJson,
+    /// A JSON subtitle is missing a `start`, `end`, or `text` key
+    MissingJsonKey,
+    /// A JSON subtitle or one of its values has the wrong type
+    InvalidJsonSubtitle,
+    /// A JSON `start` or `end` isn't a whole number of milliseconds within the range of a
+    /// [`Timestamp`][crate::Timestamp]
+    ///
+    /// This always gets reported as [`TimestampPart::Millis`]
+    InvalidJsonTimestamp,
 }
 
 impl fmt::Display for ErrorKind {
@@ -92,6 +739,78 @@ impl fmt::Display for ErrorKind {
             Self::InvalidTimestampDivider => "Invalid timestamp divider",
             Self::InvalidTimestampEnd => "Invalid ending timestamp",
             Self::TimestampEndBeforeStart => "End timestamp is before start",
+            Self::InvalidEncoding => "Invalid text encoding",
+            Self::InvalidWebVttHeader => "Invalid WebVTT header",
+            Self::MissingCueTiming => "Missing cue timing",
+            Self::InvalidWebVttTimestamp => "Invalid WebVTT timestamp",
+            Self::MissingAssEvents => "Missing [Events] section",
+            Self::InvalidAssFormat => "Invalid event format",
+            Self::InvalidAssDialogue => "Invalid dialogue event",
+            Self::InvalidAssTimestamp => "Invalid ASS timestamp",
+            Self::InvalidFrameNumber => "Invalid frame number",
+            Self::InvalidSbvTimestamp => "Invalid SBV timestamp",
+            Self::InvalidSubViewerTimestamp => "Invalid SubViewer timestamp",
+            Self::MissingTimestampComma => "Missing comma between timestamps",
+            Self::MissingSamiTag => "Missing <SAMI> tag",
+            Self::InvalidSyncStart => "Invalid SYNC start time",
+            Self::InvalidXml => "Malformed XML",
+            Self::InvalidTtmlRoot => "Invalid TTML root element",
+            Self::MissingTtmlTiming => "Missing paragraph timing",
+            Self::InvalidTtmlTime => "Invalid TTML time expression",
+            Self::MissingCsvColumn => "Missing CSV column",
+            Self::MissingCsvField => "Missing CSV field",
+            Self::UnclosedCsvQuote => "Unclosed CSV quote",
+            Self::InvalidCsvQuote => "Invalid CSV quoting",
+            Self::InvalidNumericTimestamp => "Invalid numeric timestamp",
+            Self::InvalidJson => "Invalid JSON",
+            Self::MissingJsonKey => "Missing JSON key",
+            Self::InvalidJsonSubtitle => "Invalid JSON subtitle",
+            Self::InvalidJsonTimestamp => "Invalid JSON timestamp",
+        })
+    }
+}
+
+/// Something suspicious that doesn't stop the subtitles from being parsed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Warning {
+    /// The line number of the offending cue's ID or timestamp line
+    pub line: usize,
+    /// The kind of warning
+    pub kind: WarningKind,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} on line {}", self.kind, self.line)
+    }
+}
+
+/// Describes the kind of warning
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WarningKind {
+    /// The cue doesn't have any text
+    EmptyText,
+    /// The cue starts before the previous cue
+    OutOfOrder,
+    /// The cue starts before the previous cue ends
+    Overlapping,
+    /// The ID skips ahead of the one expected after the previous cue's ID
+    IdGap,
+    /// The ID is the same as the previous cue's ID
+    DuplicateId,
+    /// The ID is before the previous cue's ID
+    IdOutOfOrder,
+}
+
+impl fmt::Display for WarningKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::EmptyText => "Cue has no text",
+            Self::OutOfOrder => "Cue starts before the previous cue",
+            Self::Overlapping => "Cue overlaps with the previous cue",
+            Self::IdGap => "ID skips ahead of the previous ID",
+            Self::DuplicateId => "ID is the same as the previous ID",
+            Self::IdOutOfOrder => "ID is before the previous ID",
         })
     }
 }
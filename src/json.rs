@@ -0,0 +1,510 @@
+//! Conversion to and from a JSON representation of the subtitles
+//!
+//! The subtitles are an array with an object for each subtitle where `start` and `end` are in
+//! milliseconds. The `id` follows the same rules as SRT IDs, and `position` only shows up for
+//! subtitles that have one. Use [`ParseOptions`] to keep the IDs when parsing
+//!
+//! ```
+//! const JSON: &str = r#"[
+//!   {"id": 1, "start": 1000, "end": 3500, "text": "Hello, world!\nSecond line"},
+//!   {"id": 2, "start": 4000, "end": 5000, "text": "Goodbye"}
+//! ]
+//! "#;
+//!
+//! let subtitles = kiss_srt::json::from_str(JSON).unwrap();
+//! assert_eq!(
+//!     kiss_srt::to_string(&subtitles),
+//!     "\
+//! 1
+//! 00:00:01,000 --> 00:00:03,500
+//! Hello, world!
+//! Second line
+//!
+//! 2
+//! 00:00:04,000 --> 00:00:05,000
+//! Goodbye
+//! ",
+//! );
+//!
+//! // And back again
+//! assert_eq!(kiss_srt::json::to_string(&subtitles), JSON);
+//! ```
+
+use std::{char, fmt, io};
+
+use crate::{
+    error::{Error, Result, Span},
+    parse::{error_at, strip_bom},
+    Position, Subtitle, Timestamp,
+};
+
+// Deeper nesting than this is rejected instead of risking a stack overflow
+const MAX_DEPTH: usize = 128;
+
+/// Attempts to parse the provided JSON text to a [`Vec`] of [`Subtitle`]s using the default
+/// [`ParseOptions`]
+///
+/// Each subtitle needs a `start` and `end` in milliseconds along with its `text`. The `id` and
+/// `position` are optional, and any other keys are ignored
+///
+/// Blank lines within the text are dropped since a blank line would end the cue early once it's
+/// rendered as SRT
+pub fn from_str(json: &str) -> Result<Vec<Subtitle>> {
+    ParseOptions::new().parse(json)
+}
+
+/// Options for tweaking how the JSON gets parsed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    keep_ids: bool,
+}
+
+impl ParseOptions {
+    /// The default options which match the behavior of [`from_str()`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether to keep the `id`s in [`Subtitle::id`]
+    ///
+    /// Off by default which leaves the IDs to be inferred when rendering like
+    /// [`crate::ParseOptions::keep_ids()`] does. IDs still have to be numbers or `null` either way
+    ///
+    /// ```
+    /// use kiss_srt::json::ParseOptions;
+    ///
+    /// const JSON: &str = r#"[{"id": 10, "start": 0, "end": 1000, "text": "Vendor IDs"}]"#;
+    ///
+    /// assert_eq!(ParseOptions::new().parse(JSON).unwrap()[0].id, None);
+    /// assert_eq!(ParseOptions::new().keep_ids(true).parse(JSON).unwrap()[0].id, Some(10));
+    /// ```
+    pub fn keep_ids(mut self, keep: bool) -> Self {
+        self.keep_ids = keep;
+        self
+    }
+
+    /// Attempts to parse the provided JSON text to a [`Vec`] of [`Subtitle`]s using these options
+    pub fn parse(&self, json: &str) -> Result<Vec<Subtitle>> {
+        let json = strip_bom(json);
+        let mut parser = Parser { json, pos: 0 };
+        let root = parser.value(0)?;
+        parser.skip_whitespace();
+        if parser.pos < json.len() {
+            return Err(parser.error());
+        }
+
+        let items = match root.value {
+            Value::Array(items) => items,
+            _ => return Err(error_at(json, root.start_span(), Error::invalid_json)),
+        };
+        items
+            .into_iter()
+            .map(|item| to_subtitle(json, *self, item))
+            .collect()
+    }
+}
+
+fn to_subtitle(json: &str, options: ParseOptions, item: Spanned<'_>) -> Result<Subtitle> {
+    let invalid =
+        |spanned: &Spanned<'_>| error_at(json, spanned.span, Error::invalid_json_subtitle);
+    let item_start = item.start_span();
+    let members = match item.value {
+        Value::Object(members) => members,
+        _ => return Err(invalid(&item)),
+    };
+
+    let (mut id, mut start, mut end, mut text, mut position) = (None, None, None, None, None);
+    for (key, value) in members {
+        match key.as_str() {
+            "id" => id = Some(value),
+            "start" => start = Some(value),
+            "end" => end = Some(value),
+            "text" => text = Some(value),
+            "position" => position = Some(value),
+            _ => {}
+        }
+    }
+    let (start, end, text) = match (start, end, text) {
+        (Some(start), Some(end), Some(text)) => (start, end, text),
+        _ => return Err(error_at(json, item_start, Error::missing_json_key)),
+    };
+
+    let to_ts = |spanned: &Spanned<'_>| {
+        spanned
+            .as_u32()
+            .and_then(Timestamp::checked_from_millis)
+            .ok_or_else(|| error_at(json, spanned.span, Error::invalid_json_ts))
+    };
+    let start_ts = to_ts(&start)?;
+    let end_ts = to_ts(&end)?;
+    if end_ts < start_ts {
+        return Err(error_at(json, end.span, Error::ts_end_before_start));
+    }
+
+    let text = match text.value {
+        // `lines()` takes care of `\r\n`s too
+        Value::String(text) => text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        _ => return Err(invalid(&text)),
+    };
+
+    let id = match id {
+        Some(Spanned {
+            value: Value::Null, ..
+        })
+        | None => None,
+        Some(id) => Some(id.as_u32().ok_or_else(|| invalid(&id))?).filter(|_| options.keep_ids),
+    };
+
+    let position = match position {
+        Some(Spanned {
+            value: Value::Null, ..
+        })
+        | None => None,
+        Some(position) => Some(to_position(&position).ok_or_else(|| invalid(&position))?),
+    };
+
+    Ok(Subtitle {
+        start: start_ts,
+        duration: end_ts - start_ts,
+        text,
+        position,
+        id,
+    })
+}
+
+fn to_position(spanned: &Spanned<'_>) -> Option<Position> {
+    let members = match &spanned.value {
+        Value::Object(members) => members,
+        _ => return None,
+    };
+    let coord = |name: &str| {
+        members
+            .iter()
+            .rev()
+            .find(|(key, _)| key == name)
+            .and_then(|(_, value)| value.as_u32())
+    };
+
+    Some(Position {
+        x1: coord("x1")?,
+        x2: coord("x2")?,
+        y1: coord("y1")?,
+        y2: coord("y2")?,
+    })
+}
+
+#[derive(Debug)]
+enum Value<'a> {
+    Null,
+    Bool,
+    // The raw number since only whole numbers are ever needed
+    Number(&'a str),
+    String(String),
+    Array(Vec<Spanned<'a>>),
+    Object(Vec<(String, Spanned<'a>)>),
+}
+
+#[derive(Debug)]
+struct Spanned<'a> {
+    value: Value<'a>,
+    // The byte offsets of the value within the whole text
+    span: Span,
+}
+
+impl Spanned<'_> {
+    fn as_u32(&self) -> Option<u32> {
+        match self.value {
+            Value::Number(num) if num.bytes().all(|b| b.is_ascii_digit()) => num.parse().ok(),
+            _ => None,
+        }
+    }
+
+    // Just the first character, which is a lot less noisy for objects and arrays
+    fn start_span(&self) -> Span {
+        Span::new(self.span.start, self.span.start + 1)
+    }
+}
+
+struct Parser<'a> {
+    json: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.json[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len()
+            - rest
+                .trim_start_matches(|c| c == ' ' || c == '\t' || c == '\n' || c == '\r')
+                .len();
+    }
+
+    fn eat(&mut self, expected: &str) -> bool {
+        let matches = self.rest().starts_with(expected);
+        if matches {
+            self.pos += expected.len();
+        }
+
+        matches
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<()> {
+        if self.eat(expected) {
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+
+    // Points at whatever character is next
+    fn error(&self) -> Error {
+        let len = self.rest().chars().next().map_or(0, char::len_utf8);
+        error_at(
+            self.json,
+            Span::new(self.pos, self.pos + len),
+            Error::invalid_json,
+        )
+    }
+
+    fn value(&mut self, depth: usize) -> Result<Spanned<'a>> {
+        self.skip_whitespace();
+        let start = self.pos;
+        if depth > MAX_DEPTH {
+            return Err(self.error());
+        }
+
+        let value = match self.rest().bytes().next() {
+            Some(b'n') => self.expect("null").map(|_| Value::Null)?,
+            Some(b't') => self.expect("true").map(|_| Value::Bool)?,
+            Some(b'f') => self.expect("false").map(|_| Value::Bool)?,
+            Some(b'"') => Value::String(self.string()?),
+            Some(b'[') => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                self.skip_whitespace();
+                if !self.eat("]") {
+                    loop {
+                        items.push(self.value(depth + 1)?);
+                        self.skip_whitespace();
+                        if self.eat("]") {
+                            break;
+                        }
+                        self.expect(",")?;
+                    }
+                }
+                Value::Array(items)
+            }
+            Some(b'{') => {
+                self.pos += 1;
+                let mut members = Vec::new();
+                self.skip_whitespace();
+                if !self.eat("}") {
+                    loop {
+                        self.skip_whitespace();
+                        if !self.rest().starts_with('"') {
+                            return Err(self.error());
+                        }
+                        let key = self.string()?;
+                        self.skip_whitespace();
+                        self.expect(":")?;
+                        members.push((key, self.value(depth + 1)?));
+                        self.skip_whitespace();
+                        if self.eat("}") {
+                            break;
+                        }
+                        self.expect(",")?;
+                    }
+                }
+                Value::Object(members)
+            }
+            Some(b'-') | Some(b'0'..=b'9') => Value::Number(self.number()?),
+            _ => return Err(self.error()),
+        };
+
+        Ok(Spanned {
+            value,
+            span: Span::new(start, self.pos),
+        })
+    }
+
+    // Of the form '-12.34e+5'
+    fn number(&mut self) -> Result<&'a str> {
+        let start = self.pos;
+        let digits = |parser: &mut Self| {
+            let rest = parser.rest();
+            let len = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            parser.pos += len;
+            len
+        };
+
+        self.eat("-");
+        if !self.eat("0") && digits(self) == 0 {
+            return Err(self.error());
+        }
+        if self.eat(".") && digits(self) == 0 {
+            return Err(self.error());
+        }
+        if self.eat("e") || self.eat("E") {
+            let _ = self.eat("+") || self.eat("-");
+            if digits(self) == 0 {
+                return Err(self.error());
+            }
+        }
+
+        Ok(&self.json[start..self.pos])
+    }
+
+    fn string(&mut self) -> Result<String> {
+        self.expect("\"")?;
+        let mut string = String::new();
+        loop {
+            let rest = self.rest();
+            let len = rest
+                .find(|c| c == '"' || c == '\\' || c < ' ')
+                .unwrap_or(rest.len());
+            string.push_str(&rest[..len]);
+            self.pos += len;
+
+            match self.rest().bytes().next() {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(string);
+                }
+                Some(b'\\') => {
+                    self.pos += 1;
+                    string.push(self.escape()?);
+                }
+                // Either a raw control character or the string was never closed
+                _ => return Err(self.error()),
+            }
+        }
+    }
+
+    // Everything after the `\` of an escape sequence
+    fn escape(&mut self) -> Result<char> {
+        let escaped = match self.rest().bytes().next() {
+            Some(b'"') => '"',
+            Some(b'\\') => '\\',
+            Some(b'/') => '/',
+            Some(b'b') => '\u{8}',
+            Some(b'f') => '\u{c}',
+            Some(b'n') => '\n',
+            Some(b'r') => '\r',
+            Some(b't') => '\t',
+            Some(b'u') => {
+                self.pos += 1;
+                let high = self.hex_escape()?;
+                let code = if (0xd800..0xdc00).contains(&high) {
+                    // The other half of a surrogate pair has to come right after
+                    self.expect("\\u")?;
+                    let low = self.hex_escape()?;
+                    if !(0xdc00..0xe000).contains(&low) {
+                        self.pos -= 4;
+                        return Err(self.error());
+                    }
+                    0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00)
+                } else {
+                    high
+                };
+                return char::from_u32(code).ok_or_else(|| {
+                    self.pos -= 4;
+                    self.error()
+                });
+            }
+            _ => return Err(self.error()),
+        };
+        self.pos += 1;
+
+        Ok(escaped)
+    }
+
+    // The four hex digits of a `\u` escape
+    fn hex_escape(&mut self) -> Result<u32> {
+        let hex = self
+            .rest()
+            .get(..4)
+            .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()));
+        match hex.and_then(|hex| u32::from_str_radix(hex, 16).ok()) {
+            Some(code) => {
+                self.pos += 4;
+                Ok(code)
+            }
+            None => Err(self.error()),
+        }
+    }
+}
+
+gen_renderers! {
+    /// Renders the `subtitles` to JSON
+    fn to_string;
+    /// Renders the `subtitles` to JSON into a [`fmt::Write`]
+    fn to_fmt_writer;
+    /// Renders the `subtitles` to JSON into an [`io::Write`]
+    fn to_writer;
+    |subtitles| Json(subtitles)
+}
+
+// A string with everything JSON needs escaped
+struct JsonString<'a>(&'a str);
+
+impl fmt::Display for JsonString<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("\"")?;
+        for c in self.0.chars() {
+            match c {
+                '"' => f.write_str("\\\"")?,
+                '\\' => f.write_str("\\\\")?,
+                '\n' => f.write_str("\\n")?,
+                '\r' => f.write_str("\\r")?,
+                '\t' => f.write_str("\\t")?,
+                c if c < ' ' => write!(f, "\\u{:04x}", c as u32)?,
+                c => write!(f, "{}", c)?,
+            }
+        }
+        f.write_str("\"")
+    }
+}
+
+// Each subtitle is kept to a single line so that diffs stay readable
+struct Json<'a>(&'a [Subtitle]);
+
+impl fmt::Display for Json<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            return f.write_str("[]\n");
+        }
+
+        f.write_str("[\n")?;
+        for (i, subtitle) in (1..).zip(self.0) {
+            write!(
+                f,
+                "  {{\"id\": {}, \"start\": {}, \"end\": {}, \"text\": {}",
+                subtitle.id.unwrap_or(i),
+                subtitle.start.total_millis(),
+                (subtitle.start + subtitle.duration).total_millis(),
+                JsonString(&subtitle.text),
+            )?;
+            if let Some(Position { x1, x2, y1, y2 }) = subtitle.position {
+                write!(
+                    f,
+                    ", \"position\": {{\"x1\": {}, \"x2\": {}, \"y1\": {}, \"y2\": {}}}",
+                    x1, x2, y1, y2
+                )?;
+            }
+            f.write_str(if i as usize == self.0.len() {
+                "}\n"
+            } else {
+                "},\n"
+            })?;
+        }
+        f.write_str("]\n")
+    }
+}
@@ -62,37 +62,232 @@
 //! There is beauty in simplicity 💕
 
 // TODO: setup github actions
+#[macro_use]
+mod macros;
+
+pub mod ass;
+pub mod csv;
+pub mod document;
+mod encoding;
 pub mod error;
+pub mod json;
+pub mod microdvd;
 mod parse;
+mod read;
 mod render;
+pub mod sami;
+pub mod sbv;
+pub mod subviewer;
+mod tags;
+mod text;
 mod time;
+pub mod transcript;
+pub mod ttml;
+pub mod webvtt;
 
-use std::fmt::Display;
+use std::{borrow::Cow, fmt::Display};
 
-pub use parse::from_str;
-pub use render::to_string;
+pub use encoding::{from_bytes, from_bytes_with, Encoding};
+pub use parse::{
+    diagnose, from_str, from_str_borrowed, parse_into, Diagnostics, ParseOptions, Parsed, Recovery,
+};
+pub use read::Reader;
+pub use render::{
+    to_fmt_writer, to_string, to_writer, try_to_string, BlankLines, LineEnding, RenderOptions,
+    Writer,
+};
+pub use text::SubtitleText;
 pub use time::{Duration, Timestamp};
 
 /// Represents a single SRT subtitle item
 ///
-/// Composed of the `start`ing timestamp, `duration` (aka end - start), and UTF-8 `text` for the
-/// subtitle. The ID is inferred as index + 1 when rendering with [`to_string`]
+/// Composed of the `start`ing timestamp, `duration` (aka end - start), UTF-8 `text`, and optional
+/// `position` for the subtitle. The ID is inferred as index + 1 when rendering with [`to_string`]
+/// unless an explicit `id` is set (see [`ParseOptions::keep_ids()`])
 ///
 /// **⚠  WARN: This type is only misuse-resistent ⚠**
 ///
 /// The `start` and `duration` are always valid, but
 /// the `text` must not contain an empty line to avoid rendering invalid subtitles. This was a
-/// deliberate tradeoff to keep rendering "infallible" while keeping the `text` easy to use
+/// deliberate tradeoff to keep rendering "infallible" while keeping the `text` easy to use. Use
+/// [`try_to_string()`] to catch these (or [`RenderOptions::blank_lines()`] to repair them), or set
+/// the `text` from a [`SubtitleText`] with [`Subtitle::new()`] or [`Subtitle::set_text()`] since it
+/// can't contain empty lines to begin with
 #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Subtitle {
     pub start: time::Timestamp,
     pub duration: time::Duration,
     pub text: String,
+    pub position: Option<Position>,
+    pub id: Option<u32>,
+}
+
+impl Subtitle {
+    /// Creates a subtitle from already validated text with no position or explicit ID
+    ///
+    /// ```
+    /// use std::convert::TryFrom;
+    /// use kiss_srt::{Duration, Subtitle, SubtitleText, Timestamp};
+    ///
+    /// let text = SubtitleText::try_from("Hello\nworld").unwrap();
+    /// let subtitle = Subtitle::new(Timestamp::from_millis(1_000), Duration::from_millis(500), text);
+    /// assert_eq!(
+    ///     kiss_srt::try_to_string(&[subtitle]).unwrap(),
+    ///     "1\n00:00:01,000 --> 00:00:01,500\nHello\nworld\n",
+    /// );
+    /// ```
+    pub fn new(start: Timestamp, duration: Duration, text: SubtitleText) -> Self {
+        Self {
+            start,
+            duration,
+            text: text.into(),
+            position: None,
+            id: None,
+        }
+    }
+
+    /// Replaces the `text` with already validated text
+    pub fn set_text(&mut self, text: SubtitleText) {
+        self.text = text.into();
+    }
+
+    /// Sets the display coordinates that get rendered after the timestamps
+    ///
+    /// ```
+    /// use kiss_srt::{Duration, Position, Subtitle, SubtitleText, Timestamp};
+    ///
+    /// let position = Position { x1: 10, x2: 20, y1: 30, y2: 40 };
+    /// let subtitle = Subtitle::new(Timestamp::default(), Duration::default(), SubtitleText::new())
+    ///     .with_position(position);
+    /// assert_eq!(subtitle.position, Some(position));
+    /// ```
+    pub fn with_position(mut self, position: Position) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    /// Sets an explicit ID to render instead of inferring one from the subtitle's index
+    ///
+    /// ```
+    /// use kiss_srt::{Duration, Subtitle, SubtitleText, Timestamp};
+    ///
+    /// let subtitle = Subtitle::new(Timestamp::default(), Duration::default(), SubtitleText::new())
+    ///     .with_id(42);
+    /// assert!(kiss_srt::to_string(&[subtitle]).starts_with("42\n"));
+    /// ```
+    pub fn with_id(mut self, id: u32) -> Self {
+        self.id = Some(id);
+        self
+    }
 }
 
 impl Display for Subtitle {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let end = self.start + self.duration;
-        write!(f, "{} --> {}\n{}", self.start, end, self.text)
+        write!(f, "{} --> {}", self.start, end)?;
+        if let Some(position) = &self.position {
+            write!(f, " {}", position)?;
+        }
+        write!(f, "\n{}", self.text)
+    }
+}
+
+/// A [`Subtitle`] whose `text` borrows from the text it was parsed from
+///
+/// Returned by [`from_str_borrowed()`]. The `text` only gets allocated when it can't be sliced
+/// directly out of the input, which is when a multi-line cue uses `\r\n` line endings or a line
+/// has extra trailing `\r`s
+///
+/// ```
+/// use std::borrow::Cow;
+///
+/// const TEXT: &str = "\
+/// 1
+/// 00:00:00,000 --> 00:00:01,000
+/// Multiple
+/// lines
+/// ";
+///
+/// let subtitles = kiss_srt::from_str_borrowed(TEXT).unwrap();
+/// match &subtitles[0].text {
+///     Cow::Borrowed(text) => assert_eq!(*text, "Multiple\nlines"),
+///     Cow::Owned(_) => panic!("Should borrow from `TEXT`"),
+/// }
+///
+/// let owned: Vec<kiss_srt::Subtitle> = subtitles.into_iter().map(Into::into).collect();
+/// assert_eq!(owned, kiss_srt::from_str(TEXT).unwrap());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BorrowedSubtitle<'a> {
+    pub start: time::Timestamp,
+    pub duration: time::Duration,
+    pub text: Cow<'a, str>,
+    pub position: Option<Position>,
+    pub id: Option<u32>,
+}
+
+impl BorrowedSubtitle<'_> {
+    /// Converts to an owned [`Subtitle`], only allocating if the `text` is still borrowed
+    pub fn into_owned(self) -> Subtitle {
+        Subtitle {
+            start: self.start,
+            duration: self.duration,
+            text: self.text.into_owned(),
+            position: self.position,
+            id: self.id,
+        }
+    }
+}
+
+impl<'a> From<BorrowedSubtitle<'a>> for Subtitle {
+    fn from(subtitle: BorrowedSubtitle<'a>) -> Self {
+        subtitle.into_owned()
+    }
+}
+
+impl<'a> From<&'a Subtitle> for BorrowedSubtitle<'a> {
+    fn from(subtitle: &'a Subtitle) -> Self {
+        Self {
+            start: subtitle.start,
+            duration: subtitle.duration,
+            text: Cow::Borrowed(&subtitle.text),
+            position: subtitle.position,
+            id: subtitle.id,
+        }
+    }
+}
+
+/// The display coordinates that some SRT files tack onto the end of the timestamp line
+///
+/// ```
+/// # use kiss_srt::Position;
+/// const TEXT: &str = "\
+/// 1
+/// 00:00:00,000 --> 00:00:01,000 X1:100 X2:600 Y1:50 Y2:80
+/// Positioned text
+/// ";
+///
+/// let subtitles = kiss_srt::from_str(TEXT).unwrap();
+/// assert_eq!(
+///     subtitles[0].position,
+///     Some(Position { x1: 100, x2: 600, y1: 50, y2: 80 }),
+/// );
+/// assert_eq!(kiss_srt::to_string(&subtitles), TEXT);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    pub x1: u32,
+    pub x2: u32,
+    pub y1: u32,
+    pub y2: u32,
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "X1:{} X2:{} Y1:{} Y2:{}",
+            self.x1, self.x2, self.y1, self.y2
+        )
     }
 }
@@ -0,0 +1,65 @@
+// Generates a format's `to_string()`, `to_fmt_writer()`, and `to_writer()` from an expression that
+// displays the `subtitles` in that format
+macro_rules! gen_renderers {
+    (
+        $( #[$string_meta:meta] )*
+        fn to_string;
+        $( #[$fmt_meta:meta] )*
+        fn to_fmt_writer;
+        $( #[$io_meta:meta] )*
+        fn to_writer;
+        |$subtitles:ident $(, $arg:ident: $arg_ty:ty )*| $display:expr
+    ) => {
+        $( #[$string_meta] )*
+        pub fn to_string($subtitles: &[Subtitle] $(, $arg: $arg_ty )*) -> String {
+            $display.to_string()
+        }
+
+        $( #[$fmt_meta] )*
+        pub fn to_fmt_writer(
+            writer: &mut impl fmt::Write,
+            $subtitles: &[Subtitle]
+            $(, $arg: $arg_ty )*
+        ) -> fmt::Result {
+            write!(writer, "{}", $display)
+        }
+
+        $( #[$io_meta] )*
+        pub fn to_writer(
+            writer: &mut impl io::Write,
+            $subtitles: &[Subtitle]
+            $(, $arg: $arg_ty )*
+        ) -> io::Result<()> {
+            write!(writer, "{}", $display)
+        }
+    };
+}
+
+// Generates an options type's `render()`, `render_to_fmt_writer()`, and `render_to_writer()` from a
+// method that displays the `subtitles` using those options. The docs get passed to `render()`
+macro_rules! gen_render_methods {
+    ( $( #[$meta:meta] )* fn $display:ident ) => {
+        $( #[$meta] )*
+        pub fn render(&self, subtitles: &[Subtitle]) -> String {
+            self.$display(subtitles).to_string()
+        }
+
+        /// Renders the `subtitles` using these options into a [`fmt::Write`]
+        pub fn render_to_fmt_writer(
+            &self,
+            writer: &mut impl fmt::Write,
+            subtitles: &[Subtitle],
+        ) -> fmt::Result {
+            write!(writer, "{}", self.$display(subtitles))
+        }
+
+        /// Renders the `subtitles` using these options into an [`io::Write`]
+        pub fn render_to_writer(
+            &self,
+            writer: &mut impl io::Write,
+            subtitles: &[Subtitle],
+        ) -> io::Result<()> {
+            write!(writer, "{}", self.$display(subtitles))
+        }
+    };
+}
@@ -0,0 +1,411 @@
+//! Conversion to and from the frame based [MicroDVD](https://en.wikipedia.org/wiki/MicroDVD)
+//! `.sub` format
+//!
+//! Each line is a single cue of the form `{start}{end}text` where `start` and `end` are frame
+//! numbers and `|` separates the lines of text. That means a [`FrameRate`] is needed to convert
+//! to and from [`Timestamp`]s
+//!
+//! ```
+//! use kiss_srt::microdvd::{self, FrameRate};
+//!
+//! const SUB: &str = "\
+//! {1}{1}25
+//! {25}{75}{y:i}First line|Second line
+//! ";
+//!
+//! // The frame rate from the header wins over the one that's passed in
+//! let subtitles = microdvd::from_str(SUB, FrameRate::FILM).unwrap();
+//! assert_eq!(
+//!     kiss_srt::to_string(&subtitles),
+//!     "1\n00:00:01,000 --> 00:00:03,000\n<i>First line</i>\nSecond line\n",
+//! );
+//!
+//! assert_eq!(microdvd::to_string(&subtitles, FrameRate::PAL), SUB);
+//! ```
+
+use std::{cmp, fmt, io};
+
+use crate::{
+    error::{Error, Result, Span},
+    parse::{strip_bom, Cursor},
+    tags::{parse_tag, Styles, TagKind},
+    Subtitle, Timestamp,
+};
+
+const MILLIS_PER_SECOND: u64 = 1_000;
+
+/// An exact frame rate stored as a fraction of frames per second
+///
+/// Converting between frames and timestamps always rounds to the nearest frame or millisecond,
+/// with ties rounding up
+///
+/// ```
+/// use kiss_srt::{microdvd::FrameRate, Timestamp};
+///
+/// let fps = FrameRate::from_fps(23.976).unwrap();
+/// // 23.976 is shorthand for NTSC film's exact rate of 24000/1001
+/// assert_eq!(fps, FrameRate::FILM_NTSC);
+/// assert_eq!(fps.to_string(), "23.976");
+///
+/// // Frame 24 is at 1001ms with this frame rate
+/// assert_eq!(fps.to_timestamp(24), Timestamp::from_millis(1_001));
+/// assert_eq!(fps.to_frame(Timestamp::from_millis(1_000)), 24);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FrameRate {
+    num: u32,
+    den: u32,
+}
+
+impl FrameRate {
+    /// NTSC film at 24000/1001 (~23.976) frames per second
+    pub const FILM_NTSC: Self = Self {
+        num: 24_000,
+        den: 1_001,
+    };
+    /// Film at 24 frames per second
+    pub const FILM: Self = Self { num: 24, den: 1 };
+    /// PAL at 25 frames per second
+    pub const PAL: Self = Self { num: 25, den: 1 };
+    /// NTSC at 30000/1001 (~29.97) frames per second
+    pub const NTSC: Self = Self {
+        num: 30_000,
+        den: 1_001,
+    };
+
+    /// Creates a frame rate of `num / den` frames per second returning `None` if either is zero
+    pub fn new(num: u32, den: u32) -> Option<Self> {
+        if num == 0 || den == 0 {
+            return None;
+        }
+
+        let divisor = gcd(num, den);
+        Some(Self {
+            num: num / divisor,
+            den: den / divisor,
+        })
+    }
+
+    /// Creates a frame rate from a decimal number of frames per second
+    ///
+    /// The rate is rounded to the nearest thousandth of a frame, except for the approximations of
+    /// the NTSC rates (like 23.976, 23.98, 29.97, and 59.94), which become their exact `/ 1001`
+    /// fractions. Returns `None` for rates that aren't positive
+    pub fn from_fps(fps: f64) -> Option<Self> {
+        if !(fps.is_finite() && fps > 0.0) {
+            return None;
+        }
+
+        for &base in &[24, 30, 48, 60, 120] {
+            let ntsc = f64::from(base) * 1_000.0 / 1_001.0;
+            if (fps - ntsc).abs() < 0.005 {
+                return Self::new(base * 1_000, 1_001);
+            }
+        }
+
+        let milli_fps = (fps * 1_000.0).round();
+        if milli_fps > f64::from(std::u32::MAX) {
+            return None;
+        }
+        Self::new(milli_fps as u32, 1_000)
+    }
+
+    /// The number of frames per second
+    pub fn fps(&self) -> f64 {
+        f64::from(self.num) / f64::from(self.den)
+    }
+
+    /// The timestamp for the start of `frame`, saturating to [`Timestamp::MAX`]
+    pub fn to_timestamp(&self, frame: u32) -> Timestamp {
+        let (num, den) = (u64::from(self.num), u64::from(self.den));
+        // Huge frames with a huge `den` can overflow even a `u64`, but those are way past the max
+        let millis = u64::from(frame)
+            .checked_mul(MILLIS_PER_SECOND * den * 2)
+            .map_or(std::u64::MAX, |scaled| {
+                scaled.saturating_add(num) / (num * 2)
+            });
+        Timestamp::from_millis(cmp::min(millis, u64::from(std::u32::MAX)) as u32)
+    }
+
+    /// The frame that's shown at `ts`
+    pub fn to_frame(&self, ts: Timestamp) -> u32 {
+        let (num, den) = (u64::from(self.num), u64::from(self.den));
+        let millis = u64::from(ts.total_millis());
+        let frame = (millis * num * 2 + MILLIS_PER_SECOND * den) / (MILLIS_PER_SECOND * den * 2);
+        cmp::min(frame, u64::from(std::u32::MAX)) as u32
+    }
+}
+
+impl fmt::Display for FrameRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (num, den) = (u64::from(self.num), u64::from(self.den));
+        let milli_fps = (num * MILLIS_PER_SECOND * 2 + den) / (den * 2);
+        write!(f, "{}", milli_fps / MILLIS_PER_SECOND)?;
+        let fraction = milli_fps % MILLIS_PER_SECOND;
+        if fraction != 0 {
+            let fraction = format!("{:03}", fraction);
+            write!(f, ".{}", fraction.trim_end_matches('0'))?;
+        }
+
+        Ok(())
+    }
+}
+
+fn gcd(mut a: u32, mut b: u32) -> u32 {
+    while b != 0 {
+        let rem = a % b;
+        a = b;
+        b = rem;
+    }
+
+    a
+}
+
+/// Attempts to parse the provided MicroDVD text to a [`Vec`] of [`Subtitle`]s
+///
+/// A `{1}{1}fps` (or `{0}{0}fps`) header on the first line overrides the provided `frame_rate`.
+/// Line breaks (`|`) become new lines, the `{y:i}`, `{y:b}`, and `{y:u}` style codes (along with
+/// a leading `/` for italics) become the SRT `<i>`, `<b>`, and `<u>` tags, and all other control
+/// codes are stripped
+pub fn from_str(text: &str, frame_rate: FrameRate) -> Result<Vec<Subtitle>> {
+    let text = strip_bom(text);
+
+    let mut frame_rate = frame_rate;
+    let mut subtitles = Vec::new();
+    let mut first = true;
+    for (line_num, line) in (1..).zip(text.lines()) {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let mut cursor = Cursor::new(line);
+        let start = parse_frame(line_num, &mut cursor)?;
+        let end_start = cursor.pos;
+        let end = parse_frame(line_num, &mut cursor)?;
+        let raw_text = &line[cursor.pos..];
+
+        if first {
+            first = false;
+            let header_fps = raw_text
+                .trim()
+                .parse()
+                .ok()
+                .and_then(FrameRate::from_fps)
+                .filter(|_| start == end && start <= 1);
+            if let Some(fps) = header_fps {
+                frame_rate = fps;
+                continue;
+            }
+        }
+
+        if end < start {
+            return Err(Error::ts_end_before_start(
+                line_num,
+                Span::new(end_start, cursor.pos),
+            ));
+        }
+
+        let start = frame_rate.to_timestamp(start);
+        subtitles.push(Subtitle {
+            start,
+            duration: frame_rate.to_timestamp(end) - start,
+            text: convert_text(raw_text),
+            position: None,
+            id: None,
+        });
+    }
+
+    Ok(subtitles)
+}
+
+// Of the form '{123}'
+fn parse_frame(line_num: usize, cursor: &mut Cursor<'_>) -> Result<u32> {
+    let start = cursor.pos;
+    let digits = cursor.line.as_bytes()[cmp::min(start + 1, cursor.line.len())..]
+        .iter()
+        .take_while(|b| b.is_ascii_digit())
+        .count();
+    let frame = cursor
+        .line
+        .get(start + 1..start + 1 + digits)
+        .and_then(|digits| digits.parse().ok());
+
+    match frame {
+        Some(frame) if cursor.eat(b"{") => {
+            cursor.pos += digits;
+            if cursor.eat(b"}") {
+                return Ok(frame);
+            }
+        }
+        _ => {}
+    }
+
+    cursor.pos = start;
+    Err(Error::invalid_frame_number(
+        line_num,
+        cursor.span(digits + 2),
+    ))
+}
+
+// Of the form 'i,b'
+fn add_styles(styles: &mut Styles, codes: &str) {
+    for code in codes.split(',') {
+        match code.trim() {
+            "i" | "I" => styles.italic = true,
+            "b" | "B" => styles.bold = true,
+            "u" | "U" => styles.underline = true,
+            _ => {}
+        }
+    }
+}
+
+// Converts the line breaks and style codes to their SRT equivalents
+fn convert_text(raw: &str) -> String {
+    let mut text = String::with_capacity(raw.len());
+    // Uppercase codes apply to every line after them
+    let mut all_lines = Styles::default();
+    for raw_line in raw.split('|') {
+        let mut styles = all_lines;
+        let mut line = String::with_capacity(raw_line.len());
+        let mut rest = raw_line;
+        if rest.starts_with('/') {
+            styles.italic = true;
+            rest = &rest[1..];
+        }
+
+        while let Some(i) = rest.find('{') {
+            line.push_str(&rest[..i]);
+            rest = &rest[i..];
+
+            // Of the form '{y:i}'
+            let code = rest.find('}').and_then(|end| {
+                let bytes = rest.as_bytes();
+                if end >= 3 && bytes[1].is_ascii_alphabetic() && bytes[2] == b':' {
+                    Some((bytes[1], &rest[3..end], end))
+                } else {
+                    None
+                }
+            });
+            match code {
+                Some((kind, codes, end)) => {
+                    match kind {
+                        b'y' => add_styles(&mut styles, codes),
+                        b'Y' => {
+                            add_styles(&mut all_lines, codes);
+                            add_styles(&mut styles, codes);
+                        }
+                        _ => {}
+                    }
+                    rest = &rest[end + 1..];
+                }
+                None => {
+                    line.push('{');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        line.push_str(rest);
+
+        if line.is_empty() {
+            continue;
+        }
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        styles.push_styled(&mut text, &line);
+    }
+
+    text
+}
+
+gen_renderers! {
+    /// Renders the `subtitles` to MicroDVD with a `{1}{1}fps` header
+    fn to_string;
+    /// Renders the `subtitles` to MicroDVD with a `{1}{1}fps` header into a [`fmt::Write`]
+    fn to_fmt_writer;
+    /// Renders the `subtitles` to MicroDVD with a `{1}{1}fps` header into an [`io::Write`]
+    fn to_writer;
+    |subtitles, frame_rate: FrameRate| Sub {
+        subtitles,
+        frame_rate,
+    }
+}
+
+struct Sub<'a> {
+    subtitles: &'a [Subtitle],
+    frame_rate: FrameRate,
+}
+
+impl fmt::Display for Sub<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            subtitles,
+            frame_rate,
+        } = self;
+
+        writeln!(f, "{{1}}{{1}}{}", frame_rate)?;
+        for subtitle in subtitles.iter() {
+            write!(
+                f,
+                "{{{}}}{{{}}}",
+                frame_rate.to_frame(subtitle.start),
+                frame_rate.to_frame(subtitle.start + subtitle.duration)
+            )?;
+            write_text(f, &subtitle.text)?;
+            f.write_str("\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+// Tags can only style whole lines, so any line that's even partially styled gets the style
+fn write_text(f: &mut fmt::Formatter<'_>, text: &str) -> fmt::Result {
+    let mut active = Styles::default();
+    for (i, raw_line) in text.split('\n').enumerate() {
+        // The styles that are active for any of the line's text
+        let mut styles = Styles::default();
+        let mut line = String::with_capacity(raw_line.len());
+        let mut rest = raw_line;
+        while !rest.is_empty() {
+            let (plain, tag) = match rest.find('<') {
+                Some(i) => (&rest[..i], parse_tag(&rest[i..])),
+                None => (rest, None),
+            };
+            if !plain.is_empty() {
+                line.push_str(plain);
+                styles.union(active);
+            }
+            rest = &rest[plain.len()..];
+
+            match tag {
+                Some(tag) => {
+                    let enabled = !tag.closing;
+                    match tag.kind {
+                        TagKind::Italic => active.italic = enabled,
+                        TagKind::Bold => active.bold = enabled,
+                        TagKind::Underline => active.underline = enabled,
+                        TagKind::Font => {}
+                    }
+                    rest = &rest[tag.len..];
+                }
+                None if !rest.is_empty() => {
+                    line.push('<');
+                    styles.union(active);
+                    rest = &rest[1..];
+                }
+                None => {}
+            }
+        }
+
+        if i != 0 {
+            f.write_str("|")?;
+        }
+        if !styles.is_empty() {
+            write!(f, "{{y:{}}}", styles.tags().join(","))?;
+        }
+        f.write_str(&line)?;
+    }
+
+    Ok(())
+}
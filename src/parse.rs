@@ -1,76 +1,241 @@
-use std::str::Bytes;
+use std::{borrow::Cow, cmp, collections::VecDeque};
 
 use crate::{
-    error::{Error, Result},
-    time::Timestamp,
-    Subtitle,
+    error::{Error, Result, Span, TimestampPart, Warning, WarningKind},
+    time::{Duration, Timestamp},
+    BorrowedSubtitle, Position, Subtitle,
 };
 
-fn parse_three_digit_ascii_num(bytes: &mut Bytes<'_>) -> Option<u16> {
-    let hundreds = bytes.next().and_then(parse_ascii_digit)?;
-    let the_rest = parse_two_digit_ascii_num(bytes)?;
+// Walks through a single line while keeping track of the position for error spans
+pub(crate) struct Cursor<'a> {
+    pub(crate) line: &'a str,
+    pub(crate) pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub(crate) fn new(line: &'a str) -> Self {
+        Self { line, pos: 0 }
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pos >= self.line.len()
+    }
+
+    /// Spans the next `len` bytes, clamped to the line and widened to a char boundary
+    pub(crate) fn span(&self, len: usize) -> Span {
+        let start = cmp::min(self.pos, self.line.len());
+        let mut end = cmp::min(start + len, self.line.len());
+        while !self.line.is_char_boundary(end) {
+            end += 1;
+        }
 
-    Some(u16::from(hundreds) * 100 + u16::from(the_rest))
+        Span::new(start, end)
+    }
+
+    /// Spans the rest of the line
+    pub(crate) fn rest(&self) -> Span {
+        self.span(self.line.len())
+    }
+
+    pub(crate) fn eat(&mut self, expected: &[u8]) -> bool {
+        let matches = self.line.as_bytes()[self.pos..].starts_with(expected);
+        if matches {
+            self.pos += expected.len();
+        }
+
+        matches
+    }
 }
 
-fn parse_two_digit_ascii_num(bytes: &mut Bytes<'_>) -> Option<u8> {
-    let (tens, ones) = match (
-        bytes.next().and_then(parse_ascii_digit),
-        bytes.next().and_then(parse_ascii_digit),
-    ) {
-        (Some(tens), Some(ones)) => Some((tens, ones)),
-        _ => None,
-    }?;
+pub(crate) type TsResult<T> = std::result::Result<T, (Span, TimestampPart)>;
 
-    Some(tens * 10 + ones)
+// Parses `min_digits..=max_digits` digits returning the number along with how many digits it had
+pub(crate) fn parse_ascii_num(
+    cursor: &mut Cursor<'_>,
+    min_digits: usize,
+    max_digits: usize,
+    max: u16,
+    part: TimestampPart,
+) -> TsResult<(u16, usize)> {
+    let digits = cursor.line.as_bytes()[cursor.pos..]
+        .iter()
+        .take(max_digits)
+        .take_while(|b| b.is_ascii_digit())
+        .count();
+    let span = cursor.span(cmp::max(digits, min_digits));
+    if digits < min_digits {
+        return Err((span, part));
+    }
+
+    let num = cursor.line.as_bytes()[cursor.pos..cursor.pos + digits]
+        .iter()
+        .fold(0, |acc, &b| acc * 10 + u16::from(b - b'0'));
+    if num >= max {
+        return Err((span, part));
+    }
+    cursor.pos += digits;
+
+    Ok((num, digits))
 }
 
-fn parse_ascii_digit(b: u8) -> Option<u8> {
-    if b.is_ascii_digit() {
-        Some(b - b'0')
+// Parses exactly `digits` digits of fractional seconds, spanning any extra digits on failure
+pub(crate) fn parse_fraction(cursor: &mut Cursor<'_>, digits: usize) -> TsResult<u16> {
+    let start = cursor.pos;
+    let max = 10u16.pow(digits as u32);
+    let (fraction, _) = parse_ascii_num(cursor, digits, digits, max, TimestampPart::Millis)?;
+    let extra_digits = cursor.line.as_bytes()[cursor.pos..]
+        .iter()
+        .take_while(|b| b.is_ascii_digit())
+        .count();
+    if extra_digits != 0 {
+        return Err((
+            Span::new(start, cursor.pos + extra_digits),
+            TimestampPart::Millis,
+        ));
+    }
+
+    Ok(fraction)
+}
+
+// Every format is fine with a leading BOM, so it gets skipped before parsing
+pub(crate) fn strip_bom(text: &str) -> &str {
+    if text.starts_with('\u{feff}') {
+        &text['\u{feff}'.len_utf8()..]
     } else {
-        None
+        text
     }
 }
 
-// Of the form '01:23:45,678'
-fn parse_ts(bytes: &mut Bytes<'_>) -> Option<Timestamp> {
-    let hours = parse_two_digit_ascii_num(bytes)?;
+// Converts byte offsets within the whole text to a line and a span within that line for formats
+// that aren't parsed line by line
+pub(crate) fn error_at(text: &str, span: Span, new: impl FnOnce(usize, Span) -> Error) -> Error {
+    let line_start = text[..span.start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = text[span.start..]
+        .find('\n')
+        .map_or(text.len(), |i| span.start + i);
+    let line_end = if text[..line_end].ends_with('\r') {
+        line_end - 1
+    } else {
+        line_end
+    };
+    let line = text[..span.start].matches('\n').count() + 1;
+    let end = span.end.min(line_end).max(span.start);
 
-    if bytes.next()? != b':' {
-        return None;
-    }
+    new(line, Span::new(span.start - line_start, end - line_start))
+}
 
-    let minutes = parse_two_digit_ascii_num(bytes)?;
+pub(crate) fn parse_separator(
+    cursor: &mut Cursor<'_>,
+    sep: u8,
+    part: TimestampPart,
+) -> TsResult<()> {
+    if cursor.eat(&[sep]) {
+        Ok(())
+    } else {
+        Err((cursor.span(1), part))
+    }
+}
 
-    if bytes.next()? != b':' {
-        return None;
+// Of the form '01:23:45,678'. Tolerant timestamps also allow for single digit hours, minutes, and
+// seconds, a '.' before the millis, and short millis like '1:2:3.45'
+pub(crate) fn parse_ts(cursor: &mut Cursor<'_>, tolerant: bool) -> TsResult<Timestamp> {
+    let min_digits = if tolerant { 1 } else { 2 };
+    let (hours, _) = parse_ascii_num(cursor, min_digits, 2, 100, TimestampPart::Hours)?;
+    parse_separator(cursor, b':', TimestampPart::HoursSeparator)?;
+    let (minutes, _) = parse_ascii_num(cursor, min_digits, 2, 60, TimestampPart::Minutes)?;
+    parse_separator(cursor, b':', TimestampPart::MinutesSeparator)?;
+    let (seconds, _) = parse_ascii_num(cursor, min_digits, 2, 60, TimestampPart::Seconds)?;
+    if !(tolerant && cursor.eat(b".")) {
+        parse_separator(cursor, b',', TimestampPart::SecondsSeparator)?;
     }
+    let min_digits = if tolerant { 1 } else { 3 };
+    let (millis, digits) = parse_ascii_num(cursor, min_digits, 3, 1_000, TimestampPart::Millis)?;
+    // Short millis are a fraction of a second, so '5' is really '500'
+    let millis = millis * 10_u16.pow(3 - digits as u32);
+
+    // All of the components were already range checked
+    Ok(
+        Timestamp::new(hours as u8, minutes as u8, seconds as u8, millis)
+            .expect("Components are in range"),
+    )
+}
+
+fn parse_ts_divider(cursor: &mut Cursor<'_>) -> std::result::Result<(), Span> {
+    const DIVIDER: &[u8] = b" --> ";
 
-    let seconds = parse_two_digit_ascii_num(bytes)?;
+    if cursor.eat(DIVIDER) {
+        Ok(())
+    } else {
+        Err(cursor.span(DIVIDER.len()))
+    }
+}
 
-    if bytes.next()? != b',' {
+// Of the form ' X1:100'
+fn parse_coord(cursor: &mut Cursor<'_>, label: &[u8]) -> Option<u32> {
+    if !cursor.eat(b" ") {
+        return None;
+    }
+    while cursor.eat(b" ") {}
+    if !cursor.eat(label) {
         return None;
     }
 
-    let millis = parse_three_digit_ascii_num(bytes)?;
+    let digits = cursor.line.as_bytes()[cursor.pos..]
+        .iter()
+        .take_while(|b| b.is_ascii_digit())
+        .count();
+    let coord = cursor.line[cursor.pos..cursor.pos + digits].parse().ok()?;
+    cursor.pos += digits;
 
-    Timestamp::new(hours, minutes, seconds, millis)
+    Some(coord)
 }
 
-fn parse_ts_divider(bytes: &mut Bytes<'_>) -> Option<()> {
-    if &[
-        bytes.next()?,
-        bytes.next()?,
-        bytes.next()?,
-        bytes.next()?,
-        bytes.next()?,
-    ] == b" --> "
-    {
-        Some(())
-    } else {
-        None
+// Of the form ' X1:100 X2:600 Y1:50 Y2:80'
+fn parse_position(cursor: &mut Cursor<'_>) -> Option<Position> {
+    Some(Position {
+        x1: parse_coord(cursor, b"X1:")?,
+        x2: parse_coord(cursor, b"X2:")?,
+        y1: parse_coord(cursor, b"Y1:")?,
+        y2: parse_coord(cursor, b"Y2:")?,
+    })
+}
+
+struct TsLine {
+    start: Timestamp,
+    end: Timestamp,
+    end_span: Span,
+    position: Option<Position>,
+    trailing_bytes: Option<Span>,
+}
+
+// Of the form '01:23:45,678 --> 01:23:45,678'
+fn parse_ts_line(line_num: usize, line: &str, tolerant: bool) -> Result<TsLine> {
+    let mut cursor = Cursor::new(line);
+    let start = parse_ts(&mut cursor, tolerant)
+        .map_err(|(span, part)| Error::invalid_ts_start(line_num, span, part))?;
+    parse_ts_divider(&mut cursor).map_err(|span| Error::invalid_ts_divider(line_num, span))?;
+    let end_start = cursor.pos;
+    let end = parse_ts(&mut cursor, tolerant)
+        .map_err(|(span, part)| Error::invalid_ts_end(line_num, span, part))?;
+    let end_span = Span::new(end_start, cursor.pos);
+    let position_start = cursor.pos;
+    let position = parse_position(&mut cursor);
+    if position.is_none() {
+        cursor.pos = position_start;
     }
+    let trailing_bytes = if cursor.is_empty() {
+        None
+    } else {
+        Some(cursor.rest())
+    };
+
+    Ok(TsLine {
+        start,
+        end,
+        end_span,
+        position,
+        trailing_bytes,
+    })
 }
 
 /// Attempts to parse the provided text to a [`Vec`] of [`Subtitle`]s
@@ -91,82 +256,661 @@ fn parse_ts_divider(bytes: &mut Bytes<'_>) -> Option<()> {
 ///             start: Timestamp::from_millis(0),
 ///             duration: Duration::from_millis(5_000),
 ///             text: String::from("Sample text"),
+///             position: None,
+///             id: None,
 ///         }
 ///     ],
 /// );
 /// ```
 pub fn from_str(subtitles: &str) -> Result<Vec<Subtitle>> {
-    let mut parsed = Vec::new();
-    let mut lines = (1..).zip(subtitles.lines());
-
-    'outer: while let Some(mut pair) = lines.next() {
-        'empty_line_eater: loop {
-            if pair.1.is_empty() {
-                pair = match lines.next() {
-                    Some(pair) => pair,
-                    None => {
-                        break 'outer;
+    ParseOptions::new().parse(subtitles).into_result()
+}
+
+/// Like [`from_str()`], but each cue's `text` borrows from the provided text when possible
+///
+/// See [`BorrowedSubtitle`] for when the `text` still has to be allocated
+///
+/// ```
+/// # use std::borrow::Cow;
+/// const TEXT: &str = "1\r\n00:00:00,000 --> 00:00:01,000\r\nOne line\r\n";
+///
+/// let subtitles = kiss_srt::from_str_borrowed(TEXT).unwrap();
+/// assert_eq!(subtitles[0].text, Cow::Borrowed("One line"));
+/// ```
+pub fn from_str_borrowed(subtitles: &str) -> Result<Vec<BorrowedSubtitle<'_>>> {
+    let mut borrowed = Vec::new();
+    for event in Cues::new((1..).zip(SourceLines::new(subtitles)), ParseOptions::new()) {
+        match event {
+            Event::Subtitle(subtitle) => borrowed.push(subtitle),
+            Event::Error(err) => return Err(err),
+            Event::Warning(_) => {}
+        }
+    }
+
+    Ok(borrowed)
+}
+
+/// Like [`from_str()`], but parses into `subtitles` to reuse its allocations
+///
+/// Existing [`Subtitle`]s get overwritten in place, so the `Vec` and each cue's `text` only need to
+/// allocate when they have to grow. Any leftover subtitles get truncated off. On an error
+/// `subtitles` is left with all of the subtitles before the error
+///
+/// ```
+/// const FIRST: &str = "1\n00:00:00,000 --> 00:00:01,000\nFirst file\n";
+/// const SECOND: &str = "1\n00:00:01,000 --> 00:00:02,000\nSecond\n";
+///
+/// let mut subtitles = Vec::new();
+/// kiss_srt::parse_into(FIRST, &mut subtitles).unwrap();
+/// let text_ptr = subtitles[0].text.as_ptr();
+///
+/// kiss_srt::parse_into(SECOND, &mut subtitles).unwrap();
+/// assert_eq!(subtitles, kiss_srt::from_str(SECOND).unwrap());
+/// // The second file's text fit in the first file's allocation
+/// assert_eq!(subtitles[0].text.as_ptr(), text_ptr);
+/// ```
+pub fn parse_into(text: &str, subtitles: &mut Vec<Subtitle>) -> Result<()> {
+    let mut len = 0;
+    let mut result = Ok(());
+    for event in Cues::new((1..).zip(SourceLines::new(text)), ParseOptions::new()) {
+        match event {
+            Event::Subtitle(subtitle) => {
+                match subtitles.get_mut(len) {
+                    Some(slot) => {
+                        slot.start = subtitle.start;
+                        slot.duration = subtitle.duration;
+                        slot.text.clear();
+                        slot.text.push_str(&subtitle.text);
+                        slot.position = subtitle.position;
+                        slot.id = subtitle.id;
                     }
-                };
-            } else {
-                break 'empty_line_eater;
+                    None => subtitles.push(subtitle.into_owned()),
+                }
+                len += 1;
             }
+            Event::Error(err) => {
+                result = Err(err);
+                break;
+            }
+            Event::Warning(_) => {}
         }
+    }
+
+    subtitles.truncate(len);
+    result
+}
+
+/// How parsing should carry on after running into a malformed cue
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Recovery {
+    /// Stop at the first error. This is what [`from_str()`] does
+    Abort,
+    /// Drop the malformed cue and resume parsing at the next one
+    Skip,
+    /// Patch up the malformed cue when possible and fall back to skipping it otherwise
+    ///
+    /// The following get repaired
+    ///
+    /// - An invalid ID is ignored (or treated as the timestamp line when it looks like one)
+    /// - An ending timestamp that's before the start results in an empty duration
+    /// - Trailing bytes on the timestamp line are ignored
+    Repair,
+}
+
+impl Default for Recovery {
+    fn default() -> Self {
+        Self::Abort
+    }
+}
+
+/// Options for tweaking how SRT text gets parsed
+///
+/// ```
+/// use kiss_srt::{ParseOptions, Recovery};
+///
+/// const TEXT: &str = "\
+/// 1
+/// 00:00:00.000 --> 00:00:01,000
+/// The starting timestamp uses a '.' instead of a ','
+///
+/// 2
+/// 00:00:02,000 --> 00:00:03,000
+/// But this one is fine
+/// ";
+///
+/// let parsed = ParseOptions::new().recovery(Recovery::Skip).parse(TEXT);
+/// assert_eq!(parsed.subtitles.len(), 1);
+/// assert_eq!(parsed.subtitles[0].text, "But this one is fine");
+/// assert_eq!(parsed.errors.len(), 1);
+/// assert_eq!(parsed.errors[0].to_string(), "Invalid starting timestamp on line 2");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+    recovery: Recovery,
+    tolerant_timestamps: bool,
+    keep_ids: bool,
+}
+
+impl ParseOptions {
+    /// The default options which match the behavior of [`from_str()`]
+    pub fn new() -> Self {
+        Self::default()
+    }
 
-        // Parse the id
-        let (line_num, line) = pair;
-        if !line.bytes().all(|b| b.is_ascii_digit()) {
-            return Err(Error::invalid_id(line_num));
+    /// Sets how to recover from malformed cues
+    pub fn recovery(mut self, recovery: Recovery) -> Self {
+        self.recovery = recovery;
+        self
+    }
+
+    /// Sets whether to accept common timestamp dialects that are found in the wild
+    ///
+    /// Off by default. When on the following variations are accepted and normalized
+    ///
+    /// - A `.` instead of a `,` before the millis (`00:00:01.500`)
+    /// - Single digit hours, minutes, and seconds (`0:00:01,500`)
+    /// - Fewer than three digits for the millis (`00:00:01,5` is the same as `00:00:01,500`)
+    ///
+    /// ```
+    /// use kiss_srt::{ParseOptions, Timestamp};
+    ///
+    /// const TEXT: &str = "\
+    /// 1
+    /// 0:00:01.5 --> 00:00:02,25
+    /// Sloppy timestamps
+    /// ";
+    ///
+    /// assert!(kiss_srt::from_str(TEXT).is_err());
+    ///
+    /// let subtitles = ParseOptions::new()
+    ///     .tolerant_timestamps(true)
+    ///     .parse(TEXT)
+    ///     .into_result()
+    ///     .unwrap();
+    /// assert_eq!(subtitles[0].start, Timestamp::from_millis(1_500));
+    /// assert_eq!(subtitles[0].start + subtitles[0].duration, Timestamp::from_millis(2_250));
+    /// ```
+    pub fn tolerant_timestamps(mut self, tolerant: bool) -> Self {
+        self.tolerant_timestamps = tolerant;
+        self
+    }
+
+    /// Sets whether to keep the original IDs in [`Subtitle::id`]
+    ///
+    /// Off by default which leaves the IDs to be inferred when rendering. IDs that are too large
+    /// to fit in a `u32` are dropped either way. Gaps, duplicates, and out of order IDs are reported
+    /// as [`Warning`]s regardless
+    ///
+    /// ```
+    /// use kiss_srt::{error::WarningKind, ParseOptions};
+    ///
+    /// const TEXT: &str = "\
+    /// 10
+    /// 00:00:00,000 --> 00:00:01,000
+    /// Vendor IDs
+    ///
+    /// 12
+    /// 00:00:01,000 --> 00:00:02,000
+    /// with a gap
+    /// ";
+    ///
+    /// let parsed = ParseOptions::new().keep_ids(true).parse(TEXT);
+    /// assert_eq!(parsed.subtitles[0].id, Some(10));
+    /// assert_eq!(parsed.subtitles[1].id, Some(12));
+    /// let warnings: Vec<_> = parsed.warnings.iter().map(|w| (w.line, w.kind)).collect();
+    /// assert_eq!(warnings, [(1, WarningKind::IdGap), (5, WarningKind::IdGap)]);
+    ///
+    /// // The original IDs are used when rendering
+    /// assert_eq!(kiss_srt::to_string(&parsed.subtitles), TEXT);
+    /// ```
+    pub fn keep_ids(mut self, keep: bool) -> Self {
+        self.keep_ids = keep;
+        self
+    }
+
+    /// Parses the provided text using these options
+    ///
+    /// Unlike [`from_str()`] this always returns whatever was parsed along with any errors that
+    /// were encountered along the way
+    pub fn parse(&self, subtitles: &str) -> Parsed {
+        let mut parsed = Parsed::default();
+        for event in Cues::new((1..).zip(SourceLines::new(subtitles)), *self) {
+            match event {
+                Event::Subtitle(subtitle) => parsed.subtitles.push(subtitle.into_owned()),
+                Event::Error(err) => parsed.errors.push(err),
+                Event::Warning(warning) => parsed.warnings.push(warning),
+            }
         }
 
-        // Parse the timestamp and duration
-        let (line_num, line) = lines
-            .next()
-            .ok_or_else(|| Error::invalid_ts_line(line_num + 1))?;
-        let mut bytes = line.bytes();
-        let start = parse_ts(&mut bytes).ok_or_else(|| Error::invalid_ts_start(line_num))?;
-        parse_ts_divider(&mut bytes).ok_or_else(|| Error::invalid_ts_divider(line_num))?;
-        let end = parse_ts(&mut bytes).ok_or_else(|| Error::invalid_ts_end(line_num))?;
-        if end < start {
-            return Err(Error::ts_end_before_start(line_num));
+        parsed
+    }
+}
+
+/// A line of text that can be joined with the lines of text that follow it into a cue's text
+pub(crate) trait Line<'a>: AsRef<str> {
+    /// Appends the line (minus any trailing `\r`s) to the non-empty lines in `text`
+    fn push_to(self, text: &mut Cow<'a, str>);
+}
+
+impl<'a> Line<'a> for String {
+    fn push_to(mut self, text: &mut Cow<'a, str>) {
+        let trimmed_len = self.trim_end_matches('\r').len();
+        self.truncate(trimmed_len);
+        if text.is_empty() {
+            *text = Cow::Owned(self);
+        } else {
+            let text = text.to_mut();
+            text.push('\n');
+            text.push_str(&self);
         }
-        let duration = end - start;
-        // Trailing bytes
-        if bytes.next().is_some() {
-            return Err(Error::invalid_ts_line(line_num));
+    }
+}
+
+/// A line that remembers where it is in the source, so that neighboring lines can be borrowed
+/// together as a single slice
+#[derive(Clone, Copy)]
+pub(crate) struct SourceLine<'a> {
+    source: &'a str,
+    start: usize,
+    end: usize,
+}
+
+impl<'a> SourceLine<'a> {
+    fn as_str(&self) -> &'a str {
+        &self.source[self.start..self.end]
+    }
+}
+
+impl AsRef<str> for SourceLine<'_> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<'a> Line<'a> for SourceLine<'a> {
+    fn push_to(self, text: &mut Cow<'a, str>) {
+        let trimmed = self.as_str().trim_end_matches('\r');
+        let joined = match text {
+            Cow::Borrowed("") => Cow::Borrowed(trimmed),
+            Cow::Borrowed(prev) => {
+                // `prev` is always a slice of `source` once it's non-empty
+                let prev_start = prev.as_ptr() as usize - self.source.as_ptr() as usize;
+                let prev_end = prev_start + prev.len();
+                if self.source.get(prev_end..self.start) == Some("\n") {
+                    Cow::Borrowed(&self.source[prev_start..self.start + trimmed.len()])
+                } else {
+                    Cow::Owned(format!("{}\n{}", prev, trimmed))
+                }
+            }
+            Cow::Owned(prev) => {
+                prev.push('\n');
+                prev.push_str(trimmed);
+                return;
+            }
+        };
+        *text = joined;
+    }
+}
+
+/// Splits text into [`SourceLine`]s the same way as [`str::lines()`]
+pub(crate) struct SourceLines<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl<'a> SourceLines<'a> {
+    pub(crate) fn new(source: &'a str) -> Self {
+        Self { source, pos: 0 }
+    }
+}
+
+impl<'a> Iterator for SourceLines<'a> {
+    type Item = SourceLine<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = &self.source[self.pos..];
+        if rest.is_empty() {
+            return None;
         }
 
-        let text = lines
-            .next()
-            .and_then(|(_, line)| {
-                let trimmed = line.trim_end_matches('\r');
-                if trimmed.is_empty() {
-                    None
+        let start = self.pos;
+        let end = match rest.find('\n') {
+            Some(offset) => {
+                self.pos += offset + 1;
+                if rest[..offset].ends_with('\r') {
+                    start + offset - 1
                 } else {
-                    Some(trimmed.to_owned())
+                    start + offset
                 }
-            })
-            .map(|mut text| {
-                for (_, line) in lines.by_ref() {
-                    let trimmed = line.trim_end_matches('\r');
-                    if trimmed.is_empty() {
-                        break;
+            }
+            None => {
+                self.pos = self.source.len();
+                self.source.len()
+            }
+        };
+
+        Some(SourceLine {
+            source: self.source,
+            start,
+            end,
+        })
+    }
+}
+
+pub(crate) enum Event<'a> {
+    Subtitle(BorrowedSubtitle<'a>),
+    Error(Error),
+    Warning(Warning),
+}
+
+/// The core of the parser which is driven by any source of numbered lines
+///
+/// Each cue's repaired errors and warnings come before the cue itself
+pub(crate) struct Cues<'a, I, L> {
+    pub(crate) lines: I,
+    peeked: Option<(usize, L)>,
+    options: ParseOptions,
+    // The start and end of the last cue for detecting warnings
+    prev: Option<(Timestamp, Timestamp)>,
+    // The ID of the last cue for detecting warnings
+    prev_id: Option<u32>,
+    pending: VecDeque<Event<'a>>,
+    done: bool,
+}
+
+impl<'a, I, L> Cues<'a, I, L>
+where
+    I: Iterator<Item = (usize, L)>,
+    L: Line<'a>,
+{
+    pub(crate) fn new(lines: I, options: ParseOptions) -> Self {
+        Self {
+            lines,
+            peeked: None,
+            options,
+            prev: None,
+            prev_id: None,
+            pending: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    fn next_line(&mut self) -> Option<(usize, L)> {
+        self.peeked.take().or_else(|| self.lines.next())
+    }
+
+    fn peek_line(&mut self) -> Option<&(usize, L)> {
+        if self.peeked.is_none() {
+            self.peeked = self.lines.next();
+        }
+
+        self.peeked.as_ref()
+    }
+
+    // Eats the rest of the current cue
+    fn skip_cue(&mut self) {
+        while let Some((_, line)) = self.next_line() {
+            if line.as_ref().trim_end_matches('\r').is_empty() {
+                break;
+            }
+        }
+    }
+
+    // Errors that got repaired and any warnings get queued up while fatal errors get returned
+    fn parse_cue(&mut self, line_num: usize, line: &str) -> Result<BorrowedSubtitle<'a>> {
+        let ParseOptions {
+            recovery,
+            tolerant_timestamps: tolerant,
+            keep_ids,
+        } = self.options;
+        let pending = &mut self.pending;
+        let mut repair = |err| {
+            if recovery == Recovery::Repair {
+                pending.push_back(Event::Error(err));
+                Ok(())
+            } else {
+                Err(err)
+            }
+        };
+
+        // Parse the id. IDs too large to represent are still valid, they just can't be kept
+        let mut id = None;
+        let mut ts_line = None;
+        if line.bytes().all(|b| b.is_ascii_digit()) {
+            id = line.parse::<u32>().ok();
+            if let Some(id) = id {
+                let kind = match self.prev_id {
+                    Some(prev_id) if id == prev_id => Some(WarningKind::DuplicateId),
+                    Some(prev_id) if id < prev_id => Some(WarningKind::IdOutOfOrder),
+                    prev_id if id > prev_id.map_or(1, |prev_id| prev_id.saturating_add(1)) => {
+                        Some(WarningKind::IdGap)
                     }
+                    _ => None,
+                };
+                if let Some(kind) = kind {
+                    self.pending.push_back(Event::Warning(Warning {
+                        line: line_num,
+                        kind,
+                    }));
+                }
+                self.prev_id = Some(id);
+            }
+        } else {
+            // Assume the broken ID was the expected one to avoid also reporting it as a gap
+            self.prev_id = Some(self.prev_id.map_or(1, |prev_id| prev_id.saturating_add(1)));
+            repair(Error::invalid_id(line_num, Span::new(0, line.len())))?;
+            // A missing ID leaves the timestamp line in its place
+            if parse_ts_line(line_num, line, tolerant).is_ok() {
+                ts_line = Some((line_num, line));
+            }
+        }
 
-                    text.push('\n');
-                    text.push_str(trimmed);
+        // Parse the timestamp and duration. An empty line is left alone so that it still ends
+        // the cue when recovering
+        let owned_ts_line;
+        let (line_num, line) = match ts_line {
+            Some(pair) => pair,
+            None => match self.peek_line() {
+                Some((_, line)) if !line.as_ref().trim_end_matches('\r').is_empty() => {
+                    owned_ts_line = self.next_line().expect("Line was peeked");
+                    (owned_ts_line.0, owned_ts_line.1.as_ref())
+                }
+                Some((line_num, line)) => {
+                    return Err(Error::invalid_ts_start(
+                        *line_num,
+                        Span::new(0, line.as_ref().len()),
+                        TimestampPart::Hours,
+                    ))
                 }
+                None => return Err(Error::invalid_ts_line(line_num + 1, Span::default())),
+            },
+        };
+        let ts_line = parse_ts_line(line_num, line, tolerant)?;
+        let mut repair = |err| {
+            if recovery == Recovery::Repair {
+                self.pending.push_back(Event::Error(err));
+                Ok(())
+            } else {
+                Err(err)
+            }
+        };
+        let duration = if ts_line.end < ts_line.start {
+            repair(Error::ts_end_before_start(line_num, ts_line.end_span))?;
+            Duration::default()
+        } else {
+            ts_line.end - ts_line.start
+        };
+        if let Some(span) = ts_line.trailing_bytes {
+            repair(Error::invalid_ts_line(line_num, span))?;
+        }
 
-                text
-            })
-            .unwrap_or_default();
+        let mut text = Cow::Borrowed("");
+        while let Some((_, line)) = self.next_line() {
+            if line.as_ref().trim_end_matches('\r').is_empty() {
+                break;
+            }
 
-        parsed.push(Subtitle {
-            start,
+            line.push_to(&mut text);
+        }
+
+        let subtitle = BorrowedSubtitle {
+            start: ts_line.start,
             duration,
             text,
-        });
+            position: ts_line.position,
+            id: if keep_ids { id } else { None },
+        };
+        let pending = &mut self.pending;
+        let mut warn = |kind| {
+            pending.push_back(Event::Warning(Warning {
+                line: line_num,
+                kind,
+            }))
+        };
+        if subtitle.text.is_empty() {
+            warn(WarningKind::EmptyText);
+        }
+        if let Some((prev_start, prev_end)) = self.prev {
+            if subtitle.start < prev_start {
+                warn(WarningKind::OutOfOrder);
+            } else if subtitle.start < prev_end {
+                warn(WarningKind::Overlapping);
+            }
+        }
+        self.prev = Some((subtitle.start, subtitle.start + subtitle.duration));
+
+        Ok(subtitle)
     }
+}
+
+impl<'a, I, L> Iterator for Cues<'a, I, L>
+where
+    I: Iterator<Item = (usize, L)>,
+    L: Line<'a>,
+{
+    type Item = Event<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(event);
+            }
+
+            if self.done {
+                return None;
+            }
+
+            let (line_num, line) = loop {
+                match self.next_line() {
+                    Some((_, line)) if line.as_ref().is_empty() => {}
+                    Some(pair) => break pair,
+                    None => {
+                        self.done = true;
+                        return None;
+                    }
+                }
+            };
+            match self.parse_cue(line_num, line.as_ref()) {
+                Ok(subtitle) => self.pending.push_back(Event::Subtitle(subtitle)),
+                Err(err) => {
+                    self.pending.push_back(Event::Error(err));
+                    if self.options.recovery == Recovery::Abort {
+                        self.done = true;
+                    } else {
+                        self.skip_cue();
+                    }
+                }
+            }
+        }
+    }
+}
 
-    Ok(parsed)
+/// The output from [`ParseOptions::parse()`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Parsed {
+    /// All of the subtitles that were parsed (or repaired)
+    pub subtitles: Vec<Subtitle>,
+    /// Every error that was encountered in the order they were found
+    pub errors: Vec<Error>,
+    /// Every warning that was encountered in the order they were found
+    pub warnings: Vec<Warning>,
+}
+
+impl Parsed {
+    /// Returns the subtitles if there were no errors or the first error otherwise
+    pub fn into_result(self) -> Result<Vec<Subtitle>> {
+        match self.errors.into_iter().next() {
+            Some(err) => Err(err),
+            None => Ok(self.subtitles),
+        }
+    }
+}
+
+/// Walks through all of the provided text reporting every problem along the way
+///
+/// This recovers from errors the same way as [`Recovery::Repair`], so a single cue can report
+/// multiple errors
+///
+/// ```
+/// use kiss_srt::error::{ErrorKind, WarningKind};
+///
+/// const TEXT: &str = "\
+/// 1
+/// 00:00:05,000 --> 00:00:06,000
+/// Fine on its own
+///
+/// two
+/// 00:00:04,000 --> 00:00:03,000
+/// Bad ID, ends before it starts, and it's before the last cue
+///
+/// 3
+/// 00:00:08.000 --> 00:00:09,000
+/// Uses a '.' instead of a ','
+/// ";
+///
+/// let diagnostics = kiss_srt::diagnose(TEXT);
+/// let errors: Vec<_> = diagnostics
+///     .errors
+///     .iter()
+///     .map(|err| (err.line, err.kind))
+///     .collect();
+/// assert_eq!(
+///     errors,
+///     [
+///         (5, ErrorKind::InvalidId),
+///         (6, ErrorKind::TimestampEndBeforeStart),
+///         (10, ErrorKind::InvalidTimestampStart),
+///     ],
+/// );
+/// let warnings: Vec<_> = diagnostics
+///     .warnings
+///     .iter()
+///     .map(|warning| (warning.line, warning.kind))
+///     .collect();
+/// assert_eq!(warnings, [(6, WarningKind::OutOfOrder)]);
+/// ```
+pub fn diagnose(subtitles: &str) -> Diagnostics {
+    let Parsed {
+        errors, warnings, ..
+    } = ParseOptions::new()
+        .recovery(Recovery::Repair)
+        .parse(subtitles);
+
+    Diagnostics { errors, warnings }
+}
+
+/// Every problem found by [`diagnose()`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Diagnostics {
+    /// Every error in the order they were found
+    pub errors: Vec<Error>,
+    /// Every warning in the order they were found
+    pub warnings: Vec<Warning>,
+}
+
+impl Diagnostics {
+    /// Returns `true` when there were no errors or warnings
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty() && self.warnings.is_empty()
+    }
 }
@@ -0,0 +1,130 @@
+use std::io::BufRead;
+
+use crate::{
+    error::ReadError,
+    parse::{Cues, Event},
+    ParseOptions, Subtitle,
+};
+
+// Numbered lines pulled from a reader. Any IO error ends the lines and gets stashed for later
+struct IoLines<R> {
+    reader: R,
+    line_num: usize,
+    error: Option<std::io::Error>,
+}
+
+impl<R: BufRead> Iterator for IoLines<R> {
+    type Item = (usize, String);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.error.is_some() {
+            return None;
+        }
+
+        let mut line = String::new();
+        match self.reader.read_line(&mut line) {
+            Ok(0) => None,
+            Ok(_) => {
+                // Strip line endings the same way as `str::lines()`
+                if line.ends_with('\n') {
+                    line.pop();
+                    if line.ends_with('\r') {
+                        line.pop();
+                    }
+                }
+
+                self.line_num += 1;
+                Some((self.line_num, line))
+            }
+            Err(err) => {
+                self.error = Some(err);
+                None
+            }
+        }
+    }
+}
+
+/// Parses subtitles one cue at a time from an [`io::BufRead`][std::io::BufRead]
+///
+/// This behaves just like [`from_str()`][crate::from_str] (including the line numbers in errors)
+/// without needing all of the text in memory at once. Iteration stops after the first error
+/// unless a different [`Recovery`][crate::Recovery] is set through [`Reader::with_options()`]
+///
+/// ```
+/// # use kiss_srt::{Duration, Timestamp, Subtitle};
+/// const TEXT: &str = "\
+/// 1
+/// 00:00:00,000 --> 00:00:05,000
+/// Sample text
+///
+/// 2
+/// 00:00:05,000 --> 00:00:06,000
+/// More sample text
+/// ";
+///
+/// let mut reader = kiss_srt::Reader::new(TEXT.as_bytes());
+/// let first = reader.next().unwrap().unwrap();
+/// assert_eq!(first.text, "Sample text");
+/// let second = reader.next().unwrap().unwrap();
+/// assert_eq!(second.start, Timestamp::from_millis(5_000));
+/// assert!(reader.next().is_none());
+/// ```
+pub struct Reader<R> {
+    cues: Cues<'static, IoLines<R>, String>,
+    done: bool,
+}
+
+impl<R: BufRead> Reader<R> {
+    /// Creates a reader that parses the same way as [`from_str()`][crate::from_str]
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, ParseOptions::new())
+    }
+
+    /// Creates a reader that parses using the provided `options`
+    ///
+    /// Recovered errors are yielded as they're encountered, and any repaired cue comes after its
+    /// errors
+    pub fn with_options(reader: R, options: ParseOptions) -> Self {
+        let lines = IoLines {
+            reader,
+            line_num: 0,
+            error: None,
+        };
+
+        Self {
+            cues: Cues::new(lines, options),
+            done: false,
+        }
+    }
+
+    /// Unwraps the underlying reader
+    pub fn into_inner(self) -> R {
+        self.cues.lines.reader
+    }
+}
+
+impl<R: BufRead> Iterator for Reader<R> {
+    type Item = Result<Subtitle, ReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        loop {
+            // IO errors take priority since the cue was cut short
+            let event = self.cues.next();
+            if let Some(err) = self.cues.lines.error.take() {
+                self.done = true;
+                return Some(Err(err.into()));
+            }
+
+            match event? {
+                Event::Subtitle(subtitle) => return Some(Ok(subtitle.into_owned())),
+                Event::Error(err) => return Some(Err(err.into())),
+                // Warnings would just pile up when streaming
+                Event::Warning(_) => {}
+            }
+        }
+    }
+}
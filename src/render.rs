@@ -1,6 +1,10 @@
-use std::fmt::Write;
+use std::{convert::TryFrom, fmt, io};
 
-use crate::Subtitle;
+use crate::{
+    error::RenderError,
+    text::{find_blank_line, is_blank},
+    Subtitle,
+};
 
 /// Renders the `subtitles` to the SRT text representation
 ///
@@ -11,6 +15,8 @@ use crate::Subtitle;
 ///         start: Timestamp::from_millis(0),
 ///         duration: Duration::from_millis(5_000),
 ///         text: String::from("Sample text"),
+///         position: None,
+///         id: None,
 ///     },
 /// ];
 /// const TEXT: &str = "\
@@ -22,16 +28,465 @@ use crate::Subtitle;
 /// assert_eq!(kiss_srt::to_string(&subtitles), TEXT);
 /// ```
 pub fn to_string(subtitles: &[Subtitle]) -> String {
-    let mut it = (1..).zip(subtitles.iter());
+    let mut rendered = String::new();
+    to_fmt_writer(&mut rendered, subtitles).expect("OOM ;-;");
+    rendered
+}
 
-    it.next()
-        .map(|(i, subtitle)| {
-            let mut rendered = format!("{}\n{}\n", i, subtitle);
+/// Renders the `subtitles` to the SRT text representation while rejecting any invalid `text`
+///
+/// Unlike [`to_string()`] this errors on the first subtitle whose `text` contains an empty line
+/// instead of silently rendering invalid SRT. See [`RenderOptions::blank_lines()`] for repairing
+/// the `text` instead
+///
+/// ```
+/// # use kiss_srt::{Duration, Timestamp, Subtitle};
+/// let subtitles = vec![
+///     Subtitle {
+///         start: Timestamp::from_millis(0),
+///         duration: Duration::from_millis(5_000),
+///         text: String::from("Fine"),
+///         position: None,
+///         id: None,
+///     },
+///     Subtitle {
+///         start: Timestamp::from_millis(5_000),
+///         duration: Duration::from_millis(5_000),
+///         text: String::from("Not\n\nfine"),
+///         position: None,
+///         id: None,
+///     },
+/// ];
+///
+/// let err = kiss_srt::try_to_string(&subtitles).unwrap_err();
+/// assert_eq!(err.index, 1);
+/// assert_eq!(err.text_line, 2);
+/// ```
+pub fn try_to_string(subtitles: &[Subtitle]) -> Result<String, RenderError> {
+    RenderOptions::new().try_render(subtitles)
+}
 
-            for (i, subtitle) in it {
-                write!(rendered, "\n{}\n{}\n", i, subtitle).expect("OOM ;-;");
+/// Renders the `subtitles` to the SRT text representation into a [`fmt::Write`]
+///
+/// ```
+/// # use kiss_srt::{Duration, Timestamp, Subtitle};
+/// # let subtitles = vec![
+/// #     Subtitle {
+/// #         start: Timestamp::from_millis(0),
+/// #         duration: Duration::from_millis(5_000),
+/// #         text: String::from("Sample text"),
+/// #         position: None,
+/// #         id: None,
+/// #     },
+/// # ];
+/// let mut rendered = String::from("Some existing text\n\n");
+/// kiss_srt::to_fmt_writer(&mut rendered, &subtitles).unwrap();
+/// assert_eq!(rendered, format!("Some existing text\n\n{}", kiss_srt::to_string(&subtitles)));
+/// ```
+pub fn to_fmt_writer(writer: &mut impl fmt::Write, subtitles: &[Subtitle]) -> fmt::Result {
+    RenderOptions::new().render_to_fmt_writer(writer, subtitles)
+}
+
+/// Renders the `subtitles` to the SRT text representation into an [`io::Write`]
+///
+/// Consider wrapping unbuffered writers (like a [`File`][std::fs::File]) in a
+/// [`BufWriter`][std::io::BufWriter] since this does many small writes
+///
+/// ```
+/// # use kiss_srt::{Duration, Timestamp, Subtitle};
+/// # let subtitles = vec![
+/// #     Subtitle {
+/// #         start: Timestamp::from_millis(0),
+/// #         duration: Duration::from_millis(5_000),
+/// #         text: String::from("Sample text"),
+/// #         position: None,
+/// #         id: None,
+/// #     },
+/// # ];
+/// let mut rendered = Vec::new();
+/// kiss_srt::to_writer(&mut rendered, &subtitles).unwrap();
+/// assert_eq!(rendered, kiss_srt::to_string(&subtitles).into_bytes());
+/// ```
+pub fn to_writer(writer: &mut impl io::Write, subtitles: &[Subtitle]) -> io::Result<()> {
+    RenderOptions::new().render_to_writer(writer, subtitles)
+}
+
+/// The line ending used when rendering
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LineEnding {
+    /// `\n`
+    Lf,
+    /// `\r\n`
+    CrLf,
+}
+
+impl LineEnding {
+    /// The line ending as text
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+        }
+    }
+}
+
+impl Default for LineEnding {
+    fn default() -> Self {
+        Self::Lf
+    }
+}
+
+/// How to handle empty lines within a subtitle's `text`
+///
+/// A subtitle with no `text` at all is fine. It's empty lines _within_ the `text` that would end
+/// the cue early
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BlankLines {
+    /// Render the `text` as is. [`RenderOptions::try_render()`] errors on empty lines instead
+    Keep,
+    /// Drop the empty lines
+    Collapse,
+    /// Replace each empty line with a single non-breaking space (`U+00A0`)
+    NonBreakingSpace,
+}
+
+impl Default for BlankLines {
+    fn default() -> Self {
+        Self::Keep
+    }
+}
+
+/// Options for tweaking how subtitles get rendered
+///
+/// ```
+/// # use kiss_srt::{Duration, Timestamp, Subtitle};
+/// use kiss_srt::{LineEnding, RenderOptions};
+///
+/// let subtitles = vec![
+///     Subtitle {
+///         start: Timestamp::from_millis(0),
+///         duration: Duration::from_millis(5_000),
+///         text: String::from("Sample\ntext"),
+///         position: None,
+///         id: None,
+///     },
+/// ];
+///
+/// let rendered = RenderOptions::new()
+///     .line_ending(LineEnding::CrLf)
+///     .bom(true)
+///     .trailing_blank_line(true)
+///     .start_id(100)
+///     .render(&subtitles);
+/// assert_eq!(
+///     rendered,
+///     "\u{feff}100\r\n00:00:00,000 --> 00:00:05,000\r\nSample\r\ntext\r\n\r\n",
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RenderOptions {
+    line_ending: LineEnding,
+    bom: bool,
+    trailing_blank_line: bool,
+    start_id: u32,
+    blank_lines: BlankLines,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            line_ending: LineEnding::default(),
+            bom: false,
+            trailing_blank_line: false,
+            start_id: 1,
+            blank_lines: BlankLines::default(),
+        }
+    }
+}
+
+impl RenderOptions {
+    /// The default options which match the behavior of [`to_string()`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the line ending. Defaults to [`LineEnding::Lf`]
+    ///
+    /// Line breaks within a subtitle's `text` get rendered with this line ending too
+    pub fn line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    /// Sets whether a UTF-8 byte order mark gets emitted before the first cue. Off by default
+    pub fn bom(mut self, bom: bool) -> Self {
+        self.bom = bom;
+        self
+    }
+
+    /// Sets whether the last cue gets followed by a blank line like all the others. Off by default
+    pub fn trailing_blank_line(mut self, trailing: bool) -> Self {
+        self.trailing_blank_line = trailing;
+        self
+    }
+
+    /// Sets the ID used for the first cue. Defaults to 1
+    ///
+    /// Explicit [`Subtitle::id`]s are still used as is. The inferred IDs stop counting up at
+    /// `u32::MAX` instead of overflowing, so any cues past that point all share it
+    pub fn start_id(mut self, id: u32) -> Self {
+        self.start_id = id;
+        self
+    }
+
+    /// Sets how empty lines within a subtitle's `text` get handled. Defaults to [`BlankLines::Keep`]
+    ///
+    /// ```
+    /// # use kiss_srt::{Duration, Timestamp, Subtitle};
+    /// use kiss_srt::{BlankLines, RenderOptions};
+    ///
+    /// let subtitles = vec![
+    ///     Subtitle {
+    ///         start: Timestamp::from_millis(0),
+    ///         duration: Duration::from_millis(5_000),
+    ///         text: String::from("Stray\n\nempty line"),
+    ///         position: None,
+    ///         id: None,
+    ///     },
+    /// ];
+    ///
+    /// let collapsed = RenderOptions::new()
+    ///     .blank_lines(BlankLines::Collapse)
+    ///     .try_render(&subtitles)
+    ///     .unwrap();
+    /// assert_eq!(collapsed, "1\n00:00:00,000 --> 00:00:05,000\nStray\nempty line\n");
+    ///
+    /// let spaced = RenderOptions::new()
+    ///     .blank_lines(BlankLines::NonBreakingSpace)
+    ///     .try_render(&subtitles)
+    ///     .unwrap();
+    /// assert_eq!(
+    ///     spaced,
+    ///     "1\n00:00:00,000 --> 00:00:05,000\nStray\n\u{a0}\nempty line\n",
+    /// );
+    /// ```
+    pub fn blank_lines(mut self, blank_lines: BlankLines) -> Self {
+        self.blank_lines = blank_lines;
+        self
+    }
+
+    /// Renders the `subtitles` using these options
+    pub fn render(&self, subtitles: &[Subtitle]) -> String {
+        let mut rendered = String::new();
+        self.render_to_fmt_writer(&mut rendered, subtitles)
+            .expect("OOM ;-;");
+        rendered
+    }
+
+    /// Renders the `subtitles` using these options while rejecting any invalid `text`
+    ///
+    /// This only errors when empty lines are set to be kept as is. See [`try_to_string()`]
+    pub fn try_render(&self, subtitles: &[Subtitle]) -> Result<String, RenderError> {
+        if self.blank_lines == BlankLines::Keep {
+            for (index, subtitle) in subtitles.iter().enumerate() {
+                if let Some(text_line) = find_blank_line(&subtitle.text) {
+                    return Err(RenderError { index, text_line });
+                }
+            }
+        }
+
+        Ok(self.render(subtitles))
+    }
+
+    /// Renders the `subtitles` using these options into a [`fmt::Write`]
+    pub fn render_to_fmt_writer(
+        &self,
+        writer: &mut impl fmt::Write,
+        subtitles: &[Subtitle],
+    ) -> fmt::Result {
+        for (i, subtitle) in subtitles.iter().enumerate() {
+            let inferred_id = u32::try_from(i)
+                .ok()
+                .and_then(|i| self.start_id.checked_add(i))
+                .unwrap_or(std::u32::MAX);
+            let cue = Cue {
+                options: self,
+                first: i == 0,
+                id: subtitle.id.unwrap_or(inferred_id),
+                subtitle,
+            };
+            write!(writer, "{}", cue)?;
+        }
+
+        Ok(())
+    }
+
+    /// Renders the `subtitles` using these options into an [`io::Write`]
+    ///
+    /// See [`to_writer()`] for details
+    pub fn render_to_writer(
+        &self,
+        writer: &mut impl io::Write,
+        subtitles: &[Subtitle],
+    ) -> io::Result<()> {
+        let mut writer = Writer::with_options(writer, *self);
+        for subtitle in subtitles {
+            writer.write(subtitle)?;
+        }
+
+        Ok(())
+    }
+}
+
+// A single cue along with whatever needs to surround it
+struct Cue<'a> {
+    options: &'a RenderOptions,
+    first: bool,
+    id: u32,
+    subtitle: &'a Subtitle,
+}
+
+impl fmt::Display for Cue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self {
+            options,
+            first,
+            id,
+            subtitle,
+        } = self;
+        let newline = options.line_ending.as_str();
+
+        if *first {
+            if options.bom {
+                f.write_str("\u{feff}")?;
             }
-            rendered
-        })
-        .unwrap_or_default()
+        } else if !options.trailing_blank_line {
+            f.write_str(newline)?;
+        }
+
+        let end = subtitle.start + subtitle.duration;
+        write!(f, "{}{}{} --> {}", id, newline, subtitle.start, end)?;
+        if let Some(position) = &subtitle.position {
+            write!(f, " {}", position)?;
+        }
+        for line in subtitle.text.split('\n') {
+            // Text with `\r\n`s shouldn't end up with doubled `\r`s
+            let line = line.trim_end_matches('\r');
+            let line = if is_blank(&subtitle.text, line) {
+                match options.blank_lines {
+                    BlankLines::Keep => line,
+                    BlankLines::Collapse => continue,
+                    BlankLines::NonBreakingSpace => "\u{a0}",
+                }
+            } else {
+                line
+            };
+            write!(f, "{}{}", newline, line)?;
+        }
+        f.write_str(newline)?;
+
+        if options.trailing_blank_line {
+            f.write_str(newline)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Incrementally renders subtitles into an [`io::Write`] one cue at a time
+///
+/// The IDs are tracked by the writer, so the output matches [`to_string()`] on all of the written
+/// subtitles. Explicit [`Subtitle::id`]s are used as is, but still count towards the running ID
+///
+/// ```
+/// # use kiss_srt::{Duration, Timestamp, Subtitle};
+/// let subtitles = vec![
+///     Subtitle {
+///         start: Timestamp::from_millis(0),
+///         duration: Duration::from_millis(5_000),
+///         text: String::from("Sample text"),
+///         position: None,
+///         id: None,
+///     },
+///     Subtitle {
+///         start: Timestamp::from_millis(5_000),
+///         duration: Duration::from_millis(1_000),
+///         text: String::from("More sample text"),
+///         position: None,
+///         id: None,
+///     },
+/// ];
+///
+/// let mut writer = kiss_srt::Writer::new(Vec::new());
+/// for subtitle in &subtitles {
+///     writer.write(subtitle).unwrap();
+/// }
+/// assert_eq!(writer.next_id(), 3);
+/// assert_eq!(writer.into_inner(), kiss_srt::to_string(&subtitles).into_bytes());
+/// ```
+#[derive(Debug)]
+pub struct Writer<W> {
+    writer: W,
+    options: RenderOptions,
+    next_id: u32,
+    wrote_any: bool,
+}
+
+impl<W: io::Write> Writer<W> {
+    /// Creates a writer that starts at an ID of 1
+    pub fn new(writer: W) -> Self {
+        Self::with_options(writer, RenderOptions::new())
+    }
+
+    /// Creates a writer that renders using the provided `options`
+    pub fn with_options(writer: W, options: RenderOptions) -> Self {
+        Self {
+            writer,
+            options,
+            next_id: options.start_id,
+            wrote_any: false,
+        }
+    }
+
+    /// Renders the next `subtitle`
+    pub fn write(&mut self, subtitle: &Subtitle) -> io::Result<()> {
+        let cue = Cue {
+            options: &self.options,
+            first: !self.wrote_any,
+            id: subtitle.id.unwrap_or(self.next_id),
+            subtitle,
+        };
+        write!(self.writer, "{}", cue)?;
+        self.next_id = self.next_id.saturating_add(1);
+        self.wrote_any = true;
+
+        Ok(())
+    }
+
+    /// The ID that will be used for the next subtitle
+    ///
+    /// This stops counting up at `u32::MAX` like [`RenderOptions::start_id()`] does
+    pub fn next_id(&self) -> u32 {
+        self.next_id
+    }
+
+    /// Flushes the underlying writer
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Returns a reference to the underlying writer
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the underlying writer
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Unwraps the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
 }
@@ -0,0 +1,349 @@
+//! Parsing for [SAMI](https://learn.microsoft.com/en-us/previous-versions/windows/desktop/dnacc/understanding-sami-1.0)
+//! (`.smi`) captions from Windows Media
+//!
+//! A SAMI file can hold several languages at once where each `<P>` is tagged with the class of
+//! the language it belongs to, so parsing gives back a separate [`Track`] for each class
+//!
+//! ```
+//! const SMI: &str = r#"<SAMI>
+//! <HEAD>
+//! <STYLE TYPE="text/css"><!--
+//! P { font-family: Arial; }
+//! .KRCC { Name: Korean; lang: ko-KR; }
+//! .ENCC { Name: English; lang: en-US; }
+//! --></STYLE>
+//! </HEAD>
+//! <BODY>
+//! <SYNC Start=1000><P Class=KRCC>안녕하세요
+//! <P Class=ENCC>Hello<br><i>there</i>
+//! <SYNC Start=2500><P Class=KRCC>&nbsp;
+//! <P Class=ENCC>&nbsp;
+//! </BODY>
+//! </SAMI>
+//! "#;
+//!
+//! let tracks = kiss_srt::sami::from_str(SMI).unwrap();
+//! assert_eq!(tracks.len(), 2);
+//!
+//! let english = &tracks[1];
+//! assert_eq!(english.class, "ENCC");
+//! assert_eq!(english.lang, Some(String::from("en-US")));
+//! assert_eq!(
+//!     kiss_srt::to_string(&english.subtitles),
+//!     "1\n00:00:01,000 --> 00:00:02,500\nHello\n<i>there</i>\n",
+//! );
+//! ```
+
+use crate::{
+    error::{Error, Result, Span},
+    parse::{error_at, strip_bom},
+    tags::{parse_tag, strip_tags, unescape, TagKind},
+    Subtitle, Timestamp,
+};
+
+/// The subtitles for a single language class
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Track {
+    /// The class name used by the `<P Class=...>` tags like `ENCC`
+    ///
+    /// This is empty for paragraphs that don't have a class
+    pub class: String,
+    /// The `Name` from the class's style rule like `English`
+    pub name: Option<String>,
+    /// The `lang` from the class's style rule like `en-US`
+    pub lang: Option<String>,
+    /// The track's subtitles sorted by their starting timestamp
+    pub subtitles: Vec<Subtitle>,
+}
+
+/// Attempts to parse the provided SAMI text to a [`Track`] for each language class
+///
+/// The tracks come in the order they're declared in the `<STYLE>` block followed by any classes
+/// that only show up in the body. Each `<SYNC>` replaces whatever was showing for the classes it
+/// has paragraphs for, so a subtitle ends at the next `<SYNC>` for its class. The last subtitle of
+/// a track ends at the next `<SYNC>` of any class instead, or lasts no time at all if it's the
+/// last `<SYNC>` in the file
+///
+/// Paragraphs that are blank or only `&nbsp;` just clear the previous subtitle. `<br>` becomes a
+/// new line, the `<i>`, `<b>`, `<u>`, and `<font>` tags are kept, and all other tags are stripped.
+/// Character references get unescaped, except for an escaped tag like `&lt;i&gt;` which keeps its
+/// `&lt;` since it would turn into real formatting in SRT otherwise
+pub fn from_str(sami: &str) -> Result<Vec<Track>> {
+    let sami = strip_bom(sami);
+    if find_tag(sami, "sami").is_none() {
+        let first_line = sami.lines().next().unwrap_or_default();
+        return Err(Error::missing_sami_tag(1, Span::new(0, first_line.len())));
+    }
+
+    let mut tracks = parse_styles(sami);
+    // The paragraphs for each track by index along with the starting time of all of the syncs
+    let mut events: Vec<(usize, u32, Option<String>)> = Vec::new();
+    let mut sync_starts = Vec::new();
+    let mut next_sync = find_tag(sami, "sync");
+    while let Some(sync_start) = next_sync {
+        let invalid_sync = |span| error_at(sami, span, Error::invalid_sync_start);
+        let name_span = Span::new(sync_start, sync_start + "<sync".len());
+        let tag_end = sami[sync_start..]
+            .find('>')
+            .map(|len| sync_start + len + 1)
+            .ok_or_else(|| invalid_sync(name_span))?;
+        let (value, offset) =
+            attr(&sami[sync_start..tag_end], "start").ok_or_else(|| invalid_sync(name_span))?;
+        let value_start = sync_start + offset;
+        let start = value
+            .parse::<u32>()
+            .map_err(|_| invalid_sync(Span::new(value_start, value_start + value.len())))?;
+        sync_starts.push(start);
+
+        next_sync = find_tag(&sami[tag_end..], "sync").map(|i| tag_end + i);
+        let content = &sami[tag_end..next_sync.unwrap_or(sami.len())];
+        let content = &content[..find_tag(content, "/body").unwrap_or(content.len())];
+        for (class, raw) in paragraphs(content) {
+            let index = match tracks
+                .iter()
+                .position(|track| track.class.eq_ignore_ascii_case(class))
+            {
+                Some(index) => index,
+                None => {
+                    tracks.push(Track {
+                        class: class.to_owned(),
+                        ..Track::default()
+                    });
+                    tracks.len() - 1
+                }
+            };
+            let text = convert_text(raw);
+            events.push((
+                index,
+                start,
+                if text.is_empty() { None } else { Some(text) },
+            ));
+        }
+    }
+
+    events.sort_by_key(|&(_, start, _)| start);
+    sync_starts.sort_unstable();
+    // Each paragraph ends where the next one in its track starts, falling back to the next sync.
+    // Walking backwards lets both get carried along instead of searching ahead for every event
+    let mut ends = vec![0; events.len()];
+    let mut next_starts = vec![None; tracks.len()];
+    let mut later_syncs = sync_starts.len();
+    for (end, &(index, start, _)) in ends.iter_mut().zip(&events).rev() {
+        while later_syncs > 0 && sync_starts[later_syncs - 1] > start {
+            later_syncs -= 1;
+        }
+        *end = next_starts[index]
+            .or_else(|| sync_starts.get(later_syncs).copied())
+            .unwrap_or(start);
+        next_starts[index] = Some(start);
+    }
+
+    for ((index, start, text), end) in events.into_iter().zip(ends) {
+        let text = match text {
+            Some(text) => text,
+            None => continue,
+        };
+
+        let start = Timestamp::from_millis(start);
+        tracks[index].subtitles.push(Subtitle {
+            start,
+            duration: Timestamp::from_millis(end) - start,
+            text,
+            position: None,
+            id: None,
+        });
+    }
+
+    Ok(tracks)
+}
+
+// Finds the byte offset of the tag with the case-insensitive `name` like `<SYNC` or `</BODY>`,
+// skipping over any comments
+fn find_tag(text: &str, name: &str) -> Option<usize> {
+    let mut pos = 0;
+    while let Some(i) = text[pos..].find('<') {
+        let start = pos + i;
+        let rest = &text[start..];
+        if rest.starts_with("<!--") {
+            pos = rest.find("-->").map_or(text.len(), |end| start + end + 3);
+        } else if starts_with_tag(rest, name) {
+            return Some(start);
+        } else {
+            pos = start + 1;
+        }
+    }
+
+    None
+}
+
+fn starts_with_tag(text: &str, name: &str) -> bool {
+    let bytes = text.as_bytes();
+    let name_end = 1 + name.len();
+    bytes.first() == Some(&b'<')
+        && bytes
+            .get(1..name_end)
+            .map_or(false, |found| found.eq_ignore_ascii_case(name.as_bytes()))
+        && bytes
+            .get(name_end)
+            .map_or(true, |b| !b.is_ascii_alphanumeric())
+}
+
+// Finds the value of the case-insensitive attribute in an HTML-ish tag along with the value's
+// offset within the tag. Values may or may not be quoted like `Start=1000` or `Class="ENCC"`
+fn attr<'a>(tag: &'a str, name: &str) -> Option<(&'a str, usize)> {
+    let tag = tag.trim_end_matches('>').trim_end_matches('/');
+    // Skip the tag's own name
+    let mut pos = tag.find(char::is_whitespace)?;
+    loop {
+        let rest = &tag[pos..];
+        pos += rest.len() - rest.trim_start().len();
+        let rest = &tag[pos..];
+        if rest.is_empty() {
+            return None;
+        }
+
+        let name_len = rest
+            .find(|c: char| c.is_whitespace() || c == '=')
+            .unwrap_or(rest.len());
+        let attr_name = &rest[..name_len];
+        pos += name_len;
+        let rest = &tag[pos..];
+        let after_ws = rest.trim_start();
+        if !after_ws.starts_with('=') {
+            // A value-less attribute
+            continue;
+        }
+        pos += rest.len() - after_ws.len() + 1;
+        let rest = &tag[pos..];
+        pos += rest.len() - rest.trim_start().len();
+        let rest = &tag[pos..];
+
+        let (value, value_start, len) = match rest.chars().next() {
+            Some(quote @ '"') | Some(quote @ '\'') => {
+                let len = rest[1..].find(quote).unwrap_or(rest.len() - 1);
+                (&rest[1..1 + len], pos + 1, len + 2)
+            }
+            _ => {
+                let len = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                (&rest[..len], pos, len)
+            }
+        };
+        if attr_name.eq_ignore_ascii_case(name) {
+            return Some((value, value_start));
+        }
+        pos = (pos + len).min(tag.len());
+    }
+}
+
+// Pulls the `Name` and `lang` out of the class rules like `.ENCC { Name: English; lang: en-US; }`
+fn parse_styles(sami: &str) -> Vec<Track> {
+    let css = match find_tag(sami, "style") {
+        Some(start) => {
+            let css = &sami[start..];
+            let css = &css[css.find('>').map_or(css.len(), |i| i + 1)..];
+            &css[..find_tag(css, "/style").unwrap_or(css.len())]
+        }
+        None => return Vec::new(),
+    };
+
+    let mut tracks = Vec::new();
+    for rule in css.split('}') {
+        let (selector, declarations) = match rule.find('{') {
+            Some(i) => (&rule[..i], &rule[i + 1..]),
+            None => continue,
+        };
+        let class = match selector.split_whitespace().last() {
+            Some(selector) if selector.starts_with('.') => &selector[1..],
+            _ => continue,
+        };
+
+        let mut track = Track {
+            class: class.to_owned(),
+            ..Track::default()
+        };
+        for declaration in declarations.split(';') {
+            let (property, value) = match declaration.find(':') {
+                Some(i) => (declaration[..i].trim(), declaration[i + 1..].trim()),
+                None => continue,
+            };
+            if property.eq_ignore_ascii_case("name") {
+                track.name = Some(value.to_owned());
+            } else if property.eq_ignore_ascii_case("lang") {
+                track.lang = Some(value.to_owned());
+            }
+        }
+        tracks.push(track);
+    }
+
+    tracks
+}
+
+// Splits a sync's content into each paragraph's class and raw text. Any text before the first
+// `<P>` is treated as a paragraph without a class
+fn paragraphs(content: &str) -> Vec<(&str, &str)> {
+    let mut paragraphs = Vec::new();
+    let first = find_tag(content, "p").unwrap_or(content.len());
+    if !content[..first].trim().is_empty() {
+        paragraphs.push(("", &content[..first]));
+    }
+
+    let mut next = Some(first).filter(|&first| first < content.len());
+    while let Some(start) = next {
+        let tag_end = content[start..]
+            .find('>')
+            .map_or(content.len(), |i| start + i + 1);
+        let class = attr(&content[start..tag_end], "class").map_or("", |(class, _)| class);
+        next = find_tag(&content[tag_end..], "p").map(|i| tag_end + i);
+        paragraphs.push((class, &content[tag_end..next.unwrap_or(content.len())]));
+    }
+
+    paragraphs
+}
+
+// Converts a paragraph's HTML to SRT text, collapsing whitespace like HTML does
+fn convert_text(raw: &str) -> String {
+    let mut converted = String::with_capacity(raw.len());
+    let mut rest = raw;
+    while let Some(i) = rest.find('<') {
+        converted.push_str(&rest[..i]);
+        rest = &rest[i..];
+
+        if rest.starts_with("<!--") {
+            rest = rest.find("-->").map_or("", |end| &rest[end + 3..]);
+            continue;
+        }
+        if starts_with_tag(rest, "br") {
+            converted.push('\n');
+        } else if let Some(tag) = parse_tag(rest) {
+            let closing = if tag.closing { "/" } else { "" };
+            match tag.kind {
+                TagKind::Italic => converted.push_str(&format!("<{}i>", closing)),
+                TagKind::Bold => converted.push_str(&format!("<{}b>", closing)),
+                TagKind::Underline => converted.push_str(&format!("<{}u>", closing)),
+                TagKind::Font => converted.push_str(&rest[..tag.len]),
+            }
+        }
+        rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+    }
+    converted.push_str(rest);
+
+    let converted = unescape(&converted);
+    let mut text = String::with_capacity(converted.len());
+    for line in converted.split('\n') {
+        // `&nbsp;` is mostly used to pad out otherwise empty paragraphs
+        if strip_tags(line).trim().is_empty() {
+            continue;
+        }
+        if !text.is_empty() {
+            text.push('\n');
+        }
+        for (i, word) in line.split_whitespace().enumerate() {
+            if i != 0 {
+                text.push(' ');
+            }
+            text.push_str(word);
+        }
+    }
+
+    text
+}
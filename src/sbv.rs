@@ -0,0 +1,167 @@
+//! Conversion to and from YouTube's SubViewer-like SBV format
+//!
+//! Each cue is a `0:00:01.000,0:00:03.000` timing line followed by its text. SBV has no
+//! formatting, so tags get stripped when exporting
+//!
+//! ```
+//! const SBV: &str = "\
+//! 0:00:01.000,0:00:03.500
+//! Hello, world!
+//! Second line
+//!
+//! 0:00:04.000,0:00:05.000
+//! Goodbye
+//! ";
+//!
+//! let subtitles = kiss_srt::sbv::from_str(SBV).unwrap();
+//! assert_eq!(
+//!     kiss_srt::to_string(&subtitles),
+//!     "\
+//! 1
+//! 00:00:01,000 --> 00:00:03,500
+//! Hello, world!
+//! Second line
+//!
+//! 2
+//! 00:00:04,000 --> 00:00:05,000
+//! Goodbye
+//! ",
+//! );
+//!
+//! // And back again
+//! assert_eq!(kiss_srt::sbv::to_string(&subtitles), SBV);
+//! ```
+
+use std::{fmt, io};
+
+use crate::{
+    error::{Error, Result, Span, TimestampPart},
+    parse::{parse_ascii_num, parse_fraction, parse_separator, strip_bom, Cursor, TsResult},
+    tags::strip_tags,
+    Subtitle, Timestamp,
+};
+
+/// Attempts to parse the provided SBV text to a [`Vec`] of [`Subtitle`]s
+pub fn from_str(sbv: &str) -> Result<Vec<Subtitle>> {
+    let sbv = strip_bom(sbv);
+    let mut lines = (1..).zip(sbv.lines());
+
+    let mut subtitles = Vec::new();
+    while let Some((line_num, line)) = lines.find(|(_, line)| !line.trim().is_empty()) {
+        let (start, end) = parse_timing_line(line_num, line, parse_ts, Error::invalid_sbv_ts)?;
+
+        let mut text = String::new();
+        for (_, line) in lines.by_ref() {
+            if line.trim().is_empty() {
+                break;
+            }
+            if !text.is_empty() {
+                text.push('\n');
+            }
+            text.push_str(line);
+        }
+
+        subtitles.push(Subtitle {
+            start,
+            duration: end - start,
+            text,
+            position: None,
+            id: None,
+        });
+    }
+
+    Ok(subtitles)
+}
+
+// Of the form '0:01:23.456'
+fn parse_ts(cursor: &mut Cursor<'_>) -> TsResult<Timestamp> {
+    let (hours, _) = parse_ascii_num(cursor, 1, 2, 100, TimestampPart::Hours)?;
+    parse_separator(cursor, b':', TimestampPart::HoursSeparator)?;
+    let (minutes, _) = parse_ascii_num(cursor, 2, 2, 60, TimestampPart::Minutes)?;
+    parse_separator(cursor, b':', TimestampPart::MinutesSeparator)?;
+    let (seconds, _) = parse_ascii_num(cursor, 2, 2, 60, TimestampPart::Seconds)?;
+    parse_separator(cursor, b'.', TimestampPart::SecondsSeparator)?;
+    let millis = parse_fraction(cursor, 3)?;
+
+    // All of the components were already range checked
+    Ok(
+        Timestamp::new(hours as u8, minutes as u8, seconds as u8, millis)
+            .expect("Components are in range"),
+    )
+}
+
+// Of the form '<start>,<end>' which SubViewer shares with its own timestamps
+pub(crate) fn parse_timing_line(
+    line_num: usize,
+    line: &str,
+    parse_ts: fn(&mut Cursor<'_>) -> TsResult<Timestamp>,
+    invalid_ts: fn(usize, Span, TimestampPart) -> Error,
+) -> Result<(Timestamp, Timestamp)> {
+    let mut cursor = Cursor::new(line);
+    let start = parse_ts(&mut cursor).map_err(|(span, part)| invalid_ts(line_num, span, part))?;
+    if !cursor.eat(b",") {
+        return Err(Error::missing_ts_comma(line_num, cursor.span(1)));
+    }
+
+    let end_start = cursor.pos;
+    let end = parse_ts(&mut cursor).map_err(|(span, part)| invalid_ts(line_num, span, part))?;
+    if end < start {
+        return Err(Error::ts_end_before_start(
+            line_num,
+            Span::new(end_start, cursor.pos),
+        ));
+    }
+    if !line[cursor.pos..].trim_end().is_empty() {
+        return Err(invalid_ts(line_num, cursor.rest(), TimestampPart::Millis));
+    }
+
+    Ok((start, end))
+}
+
+gen_renderers! {
+    /// Renders the `subtitles` to SBV
+    fn to_string;
+    /// Renders the `subtitles` to SBV into a [`fmt::Write`]
+    fn to_fmt_writer;
+    /// Renders the `subtitles` to SBV into an [`io::Write`]
+    fn to_writer;
+    |subtitles| Sbv(subtitles)
+}
+
+// An SBV timestamp which doesn't pad the hours
+struct SbvTimestamp(Timestamp);
+
+impl fmt::Display for SbvTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self(ts) = self;
+        write!(
+            f,
+            "{}:{:02}:{:02}.{:03}",
+            ts.hours(),
+            ts.minutes(),
+            ts.seconds(),
+            ts.millis()
+        )
+    }
+}
+
+struct Sbv<'a>(&'a [Subtitle]);
+
+impl fmt::Display for Sbv<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, subtitle) in self.0.iter().enumerate() {
+            if i != 0 {
+                f.write_str("\n")?;
+            }
+            writeln!(
+                f,
+                "{},{}",
+                SbvTimestamp(subtitle.start),
+                SbvTimestamp(subtitle.start + subtitle.duration)
+            )?;
+            writeln!(f, "{}", strip_tags(&subtitle.text))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,163 @@
+//! Conversion to and from the SubViewer 2.0 format
+//!
+//! The `[INFORMATION]` header and any other `[TAG]` lines before the cues get skipped when
+//! importing. Cues are a `00:00:01.00,00:00:03.00` timing line followed by text that uses `[br]`
+//! for its line breaks
+//!
+//! ```
+//! const SUB: &str = "\
+//! [INFORMATION]
+//! [TITLE]Some movie
+//! [END INFORMATION]
+//! [SUBTITLE]
+//! [COLF]&HFFFFFF,[STYLE]no,[SIZE]18,[FONT]Arial
+//! 00:00:01.50,00:00:03.00
+//! Hello, world![br]Second line
+//! ";
+//!
+//! let subtitles = kiss_srt::subviewer::from_str(SUB).unwrap();
+//! assert_eq!(
+//!     kiss_srt::to_string(&subtitles),
+//!     "1\n00:00:01,500 --> 00:00:03,000\nHello, world!\nSecond line\n",
+//! );
+//!
+//! // And back again
+//! let sub = kiss_srt::subviewer::to_string(&subtitles);
+//! assert!(sub.ends_with("00:00:01.50,00:00:03.00\nHello, world![br]Second line\n"));
+//! ```
+
+use std::{fmt, io};
+
+use crate::{
+    error::{Error, Result, TimestampPart},
+    parse::{parse_ascii_num, parse_fraction, parse_separator, strip_bom, Cursor, TsResult},
+    sbv::parse_timing_line,
+    tags::strip_tags,
+    Subtitle, Timestamp,
+};
+
+/// Attempts to parse the provided SubViewer 2.0 text to a [`Vec`] of [`Subtitle`]s
+///
+/// Timestamps only have centiseconds, and the `[br]` line breaks become new lines
+pub fn from_str(sub: &str) -> Result<Vec<Subtitle>> {
+    let sub = strip_bom(sub);
+    let mut lines = (1..).zip(sub.lines());
+
+    let mut subtitles = Vec::new();
+    while let Some((line_num, line)) = lines.find(|(_, line)| !line.trim().is_empty()) {
+        // Header tags like `[TITLE]` never show up where a timing line should be
+        if line.starts_with('[') {
+            continue;
+        }
+        let (start, end) =
+            parse_timing_line(line_num, line, parse_ts, Error::invalid_subviewer_ts)?;
+
+        let mut text = String::new();
+        for (_, line) in lines.by_ref() {
+            if line.trim().is_empty() {
+                break;
+            }
+            for text_line in line.split("[br]").filter(|line| !line.is_empty()) {
+                if !text.is_empty() {
+                    text.push('\n');
+                }
+                text.push_str(text_line);
+            }
+        }
+
+        subtitles.push(Subtitle {
+            start,
+            duration: end - start,
+            text,
+            position: None,
+            id: None,
+        });
+    }
+
+    Ok(subtitles)
+}
+
+// Of the form '01:23:45.67'
+fn parse_ts(cursor: &mut Cursor<'_>) -> TsResult<Timestamp> {
+    let (hours, _) = parse_ascii_num(cursor, 2, 2, 100, TimestampPart::Hours)?;
+    parse_separator(cursor, b':', TimestampPart::HoursSeparator)?;
+    let (minutes, _) = parse_ascii_num(cursor, 2, 2, 60, TimestampPart::Minutes)?;
+    parse_separator(cursor, b':', TimestampPart::MinutesSeparator)?;
+    let (seconds, _) = parse_ascii_num(cursor, 2, 2, 60, TimestampPart::Seconds)?;
+    parse_separator(cursor, b'.', TimestampPart::SecondsSeparator)?;
+    let centis = parse_fraction(cursor, 2)?;
+
+    // All of the components were already range checked
+    Ok(
+        Timestamp::new(hours as u8, minutes as u8, seconds as u8, centis * 10)
+            .expect("Components are in range"),
+    )
+}
+
+gen_renderers! {
+    /// Renders the `subtitles` to SubViewer 2.0 with a blank `[INFORMATION]` header
+    fn to_string;
+    /// Renders the `subtitles` to SubViewer 2.0 into a [`fmt::Write`]
+    fn to_fmt_writer;
+    /// Renders the `subtitles` to SubViewer 2.0 into an [`io::Write`]
+    fn to_writer;
+    |subtitles| SubViewer(subtitles)
+}
+
+// A SubViewer timestamp rounded to the nearest centisecond
+struct SubViewerTimestamp(Timestamp);
+
+impl fmt::Display for SubViewerTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self(ts) = self;
+        let (hours, minutes, seconds, centis) = ts.centi_parts();
+        write!(
+            f,
+            "{:02}:{:02}:{:02}.{:02}",
+            hours, minutes, seconds, centis
+        )
+    }
+}
+
+struct SubViewer<'a>(&'a [Subtitle]);
+
+impl fmt::Display for SubViewer<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(
+            "\
+[INFORMATION]
+[TITLE]
+[AUTHOR]
+[SOURCE]
+[PRG]
+[FILEPATH]
+[DELAY]0
+[CD TRACK]0
+[COMMENT]
+[END INFORMATION]
+[SUBTITLE]
+[COLF]&HFFFFFF,[STYLE]no,[SIZE]18,[FONT]Arial
+",
+        )?;
+
+        for subtitle in self.0 {
+            writeln!(
+                f,
+                "\n{},{}",
+                SubViewerTimestamp(subtitle.start),
+                SubViewerTimestamp(subtitle.start + subtitle.duration)
+            )?;
+            // SubViewer has no formatting, so all that's left is swapping the line breaks
+            let text = strip_tags(&subtitle.text);
+            for (i, line) in text.split('\n').enumerate() {
+                if i != 0 {
+                    f.write_str("[br]")?;
+                }
+                f.write_str(line)?;
+            }
+            f.write_str("\n")?;
+        }
+
+        Ok(())
+    }
+}
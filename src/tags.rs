@@ -0,0 +1,179 @@
+//! The handful of formatting tags that show up in SRT text along with the markup helpers that
+//! other formats need to convert to them
+
+use std::borrow::Cow;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum TagKind {
+    Italic,
+    Bold,
+    Underline,
+    Font,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct Tag {
+    pub(crate) kind: TagKind,
+    pub(crate) closing: bool,
+    /// How many bytes the tag takes up
+    pub(crate) len: usize,
+}
+
+/// Parses the tag at the start of `text` (if it is one) like `<i>`, `</B>`, or `<font color="red">`
+pub(crate) fn parse_tag(text: &str) -> Option<Tag> {
+    if !text.starts_with('<') {
+        return None;
+    }
+    let end = text.find('>')?;
+    let tag = &text[1..end];
+    let (closing, name) = if tag.starts_with('/') {
+        (true, &tag[1..])
+    } else {
+        (false, tag)
+    };
+
+    let name = name.to_ascii_lowercase();
+    let kind = match name.as_str() {
+        "i" => TagKind::Italic,
+        "b" => TagKind::Bold,
+        "u" => TagKind::Underline,
+        "font" => TagKind::Font,
+        _ if !closing && name.starts_with("font ") => TagKind::Font,
+        _ => return None,
+    };
+
+    Some(Tag {
+        kind,
+        closing,
+        len: end + 1,
+    })
+}
+
+/// Which of the italic, bold, and underline styles are enabled
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct Styles {
+    pub(crate) italic: bool,
+    pub(crate) bold: bool,
+    pub(crate) underline: bool,
+}
+
+impl Styles {
+    pub(crate) fn is_empty(&self) -> bool {
+        !(self.italic || self.bold || self.underline)
+    }
+
+    pub(crate) fn union(&mut self, other: Self) {
+        self.italic |= other.italic;
+        self.bold |= other.bold;
+        self.underline |= other.underline;
+    }
+
+    /// The names of the enabled tags in the order they get opened
+    pub(crate) fn tags(&self) -> Vec<&'static str> {
+        [(self.italic, "i"), (self.bold, "b"), (self.underline, "u")]
+            .iter()
+            .filter(|(enabled, _)| *enabled)
+            .map(|(_, tag)| *tag)
+            .collect()
+    }
+
+    /// Pushes `content` onto `text` wrapped in the enabled tags like `<i><b>content</b></i>`
+    pub(crate) fn push_styled(&self, text: &mut String, content: &str) {
+        let tags = self.tags();
+        for tag in &tags {
+            text.push('<');
+            text.push_str(tag);
+            text.push('>');
+        }
+        text.push_str(content);
+        for tag in tags.iter().rev() {
+            text.push_str("</");
+            text.push_str(tag);
+            text.push('>');
+        }
+    }
+}
+
+/// Removes all of the tags from `text`, leaving anything that only looks like a tag alone
+pub(crate) fn strip_tags(text: &str) -> Cow<'_, str> {
+    if !text.contains('<') {
+        return Cow::Borrowed(text);
+    }
+
+    let mut stripped = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(i) = rest.find('<') {
+        stripped.push_str(&rest[..i]);
+        rest = &rest[i..];
+        match parse_tag(rest) {
+            Some(tag) => rest = &rest[tag.len..],
+            None => {
+                stripped.push('<');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    stripped.push_str(rest);
+    Cow::Owned(stripped)
+}
+
+/// Unescapes the XML entities, `&nbsp;`, and numeric character references like `&#x266A;`
+///
+/// See [`keep_escaped_tags()`] for the one exception
+pub(crate) fn unescape(raw: &str) -> String {
+    let mut text = String::with_capacity(raw.len());
+    let mut escaped_lts = Vec::new();
+    let mut rest = raw;
+    while let Some(i) = rest.find('&') {
+        text.push_str(&rest[..i]);
+        rest = &rest[i..];
+
+        let unescaped = rest.find(';').and_then(|end| {
+            let name = &rest[1..end];
+            let c = match name {
+                "amp" => '&',
+                "lt" => '<',
+                "gt" => '>',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => '\u{a0}',
+                _ if name.starts_with("#x") => {
+                    std::char::from_u32(u32::from_str_radix(&name[2..], 16).ok()?)?
+                }
+                _ if name.starts_with('#') => std::char::from_u32(name[1..].parse().ok()?)?,
+                _ => return None,
+            };
+            Some((c, end + 1))
+        });
+        match unescaped {
+            Some((c, len)) => {
+                if c == '<' {
+                    escaped_lts.push(text.len());
+                }
+                text.push(c);
+                rest = &rest[len..];
+            }
+            None => {
+                text.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    text.push_str(rest);
+    keep_escaped_tags(&mut text, &escaped_lts);
+    text
+}
+
+/// Re-escapes the unescaped `<`s at `positions` that would start a tag like `<i>`
+///
+/// SRT has no way to escape markup, so text that was escaped in the source format would turn into
+/// formatting otherwise. These stay as a literal `&lt;` instead
+pub(crate) fn keep_escaped_tags(text: &mut String, positions: &[usize]) {
+    for &pos in positions.iter().rev() {
+        if parse_tag(&text[pos..]).is_some() {
+            text.replace_range(pos..=pos, "&lt;");
+        }
+    }
+}
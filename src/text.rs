@@ -0,0 +1,152 @@
+use std::{convert::TryFrom, fmt, ops::Deref};
+
+use crate::error::TextError;
+
+/// A subtitle's text that is guaranteed to not contain any empty lines
+///
+/// This makes the invariant described on [`Subtitle`][crate::Subtitle] impossible to violate.
+/// Pass it to [`Subtitle::new()`][crate::Subtitle::new] or
+/// [`Subtitle::set_text()`][crate::Subtitle::set_text] to use it
+///
+/// ```
+/// use std::convert::TryFrom;
+/// use kiss_srt::SubtitleText;
+///
+/// let mut text = SubtitleText::try_from("First line").unwrap();
+/// text.push_line("Second line").unwrap();
+/// assert_eq!(text.lines().collect::<Vec<_>>(), ["First line", "Second line"]);
+///
+/// // Empty lines are rejected
+/// assert!(text.push_line("").is_err());
+/// assert!(SubtitleText::try_from(String::from("Sneaky\n\nempty line")).is_err());
+///
+/// // Derefs to a `str`
+/// assert!(text.starts_with("First"));
+/// let text: String = text.into();
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SubtitleText {
+    text: String,
+    // Kept around so that pushing a line doesn't have to recount all of the lines before it
+    num_lines: usize,
+}
+
+impl SubtitleText {
+    /// Creates an empty text
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Iterates over each line of the text
+    pub fn lines(&self) -> std::str::Lines<'_> {
+        self.text.lines()
+    }
+
+    /// Returns the number of lines in the text
+    pub fn num_lines(&self) -> usize {
+        self.num_lines
+    }
+
+    /// Appends `line` to the end of the text
+    ///
+    /// The `line` may span several lines itself, but none of them can be empty
+    pub fn push_line(&mut self, line: &str) -> Result<(), TextError> {
+        let offset = self.num_lines;
+        if line.is_empty() {
+            return Err(TextError {
+                text_line: offset + 1,
+            });
+        }
+        if let Some(text_line) = find_blank_line(line) {
+            return Err(TextError {
+                text_line: offset + text_line,
+            });
+        }
+
+        if !self.text.is_empty() {
+            self.text.push('\n');
+        }
+        self.text.push_str(line);
+        self.num_lines += count_lines(line);
+        Ok(())
+    }
+
+    /// Returns the text as a `str`
+    pub fn as_str(&self) -> &str {
+        &self.text
+    }
+
+    /// Unwraps the underlying `String`
+    pub fn into_string(self) -> String {
+        self.text
+    }
+}
+
+impl TryFrom<String> for SubtitleText {
+    type Error = TextError;
+
+    fn try_from(text: String) -> Result<Self, Self::Error> {
+        match find_blank_line(&text) {
+            Some(text_line) => Err(TextError { text_line }),
+            None => Ok(Self {
+                num_lines: count_lines(&text),
+                text,
+            }),
+        }
+    }
+}
+
+impl TryFrom<&str> for SubtitleText {
+    type Error = TextError;
+
+    fn try_from(text: &str) -> Result<Self, Self::Error> {
+        Self::try_from(String::from(text))
+    }
+}
+
+impl From<SubtitleText> for String {
+    fn from(text: SubtitleText) -> Self {
+        text.text
+    }
+}
+
+impl Deref for SubtitleText {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.text
+    }
+}
+
+impl AsRef<str> for SubtitleText {
+    fn as_ref(&self) -> &str {
+        &self.text
+    }
+}
+
+impl fmt::Display for SubtitleText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+fn count_lines(text: &str) -> usize {
+    if text.is_empty() {
+        0
+    } else {
+        text.split('\n').count()
+    }
+}
+
+// Empty text is fine, but any empty lines within the text would end the cue early
+pub(crate) fn is_blank(text: &str, line: &str) -> bool {
+    !text.is_empty() && line.trim_end_matches('\r').is_empty()
+}
+
+// Returns the line number of the first empty line in the text
+pub(crate) fn find_blank_line(text: &str) -> Option<usize> {
+    (1..)
+        .zip(text.split('\n'))
+        .find(|(_, line)| is_blank(text, line))
+        .map(|(line_num, _)| line_num)
+}
@@ -197,6 +197,18 @@ impl Timestamp {
     pub fn total_millis(&self) -> u32 {
         self.0
     }
+
+    // Rounds to the nearest centisecond for the formats that only have two fractional digits and
+    // splits it into hours, minutes, seconds, and centis. Rounding up can't go past the max hours
+    pub(crate) fn centi_parts(&self) -> (u32, u32, u32, u32) {
+        let centis = cmp::min((self.0 + 5) / 10, Self::MAX.0 / 10);
+        (
+            centis / 360_000,
+            centis / 6_000 % 60,
+            centis / 100 % 60,
+            centis % 100,
+        )
+    }
 }
 
 impl Add for Timestamp {
@@ -0,0 +1,198 @@
+//! Rendering subtitles as a plain-text transcript
+//!
+//! Cues get merged into paragraphs wherever there isn't much of a gap between them, and all of the
+//! markup tags and line breaks within the cues are dropped
+//!
+//! ```
+//! const TEXT: &str = "\
+//! 1
+//! 00:00:01,000 --> 00:00:02,000
+//! Hello there.
+//!
+//! 2
+//! 00:00:02,500 --> 00:00:04,000
+//! How are <i>you</i>
+//! doing?
+//!
+//! 3
+//! 00:00:10,000 --> 00:00:11,000
+//! Much later
+//! ";
+//!
+//! let subtitles = kiss_srt::from_str(TEXT).unwrap();
+//! assert_eq!(
+//!     kiss_srt::transcript::to_string(&subtitles),
+//!     "Hello there. How are you doing?\n\nMuch later\n",
+//! );
+//! ```
+
+use std::{fmt, io};
+
+use crate::{tags::strip_tags, Duration, Subtitle, Timestamp};
+
+const DEFAULT_PARAGRAPH_GAP: u32 = 2_000;
+
+gen_renderers! {
+    /// Renders the `subtitles` to a transcript using the default [`RenderOptions`]
+    fn to_string;
+    /// Renders the `subtitles` to a transcript into a [`fmt::Write`] using the default
+    /// [`RenderOptions`]
+    fn to_fmt_writer;
+    /// Renders the `subtitles` to a transcript into an [`io::Write`] using the default
+    /// [`RenderOptions`]
+    fn to_writer;
+    |subtitles| RenderOptions::new().transcript(subtitles)
+}
+
+/// Options for tweaking how the transcript gets laid out
+///
+/// ```
+/// use kiss_srt::{transcript::RenderOptions, Duration};
+///
+/// const TEXT: &str = "\
+/// 1
+/// 00:00:01,000 --> 00:00:02,000
+/// First
+///
+/// 2
+/// 00:00:05,000 --> 00:00:06,000
+/// Second
+///
+/// 3
+/// 00:01:23,500 --> 00:01:24,000
+/// Third
+/// ";
+///
+/// let subtitles = kiss_srt::from_str(TEXT).unwrap();
+/// let options = RenderOptions::new()
+///     .paragraph_gap(Duration::from_millis(5_000))
+///     .timestamp_interval(Some(Duration::from_millis(60_000)));
+/// assert_eq!(
+///     options.render(&subtitles),
+///     "[00:00:01] First Second\n\n[00:01:23] Third\n",
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RenderOptions {
+    paragraph_gap: Duration,
+    timestamp_interval: Option<Duration>,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        Self {
+            paragraph_gap: Duration::from_millis(DEFAULT_PARAGRAPH_GAP),
+            timestamp_interval: None,
+        }
+    }
+}
+
+impl RenderOptions {
+    /// The default options which match the behavior of [`to_string()`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how long the gap between cues has to be for them to start a new paragraph. Two
+    /// seconds by default
+    ///
+    /// A gap of zero puts every cue in its own paragraph
+    pub fn paragraph_gap(mut self, gap: Duration) -> Self {
+        self.paragraph_gap = gap;
+        self
+    }
+
+    /// Sets how often to mark the time with timestamps like `[00:01:23]`. Off by default
+    ///
+    /// The first cue always gets marked, and after that the next cue that starts at least
+    /// `interval` after the last mark gets marked with its own starting time
+    pub fn timestamp_interval(mut self, interval: Option<Duration>) -> Self {
+        self.timestamp_interval = interval;
+        self
+    }
+
+    gen_render_methods! {
+        /// Renders the `subtitles` using these options
+        fn transcript
+    }
+
+    fn transcript<'a>(&'a self, subtitles: &'a [Subtitle]) -> Transcript<'a> {
+        Transcript {
+            options: self,
+            subtitles,
+        }
+    }
+}
+
+// A timestamp rounded down to the second like `[00:01:23]`
+struct Mark(Timestamp);
+
+impl fmt::Display for Mark {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self(ts) = self;
+        write!(
+            f,
+            "[{:02}:{:02}:{:02}]",
+            ts.hours(),
+            ts.minutes(),
+            ts.seconds()
+        )
+    }
+}
+
+struct Transcript<'a> {
+    options: &'a RenderOptions,
+    subtitles: &'a [Subtitle],
+}
+
+impl fmt::Display for Transcript<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let RenderOptions {
+            paragraph_gap,
+            timestamp_interval,
+        } = *self.options;
+
+        let mut prev_end: Option<Timestamp> = None;
+        let mut last_mark: Option<Timestamp> = None;
+        for subtitle in self.subtitles {
+            let text = strip_tags(&subtitle.text);
+            let mut words = text.split_whitespace().peekable();
+            // Empty cues don't get to break up a paragraph either
+            if words.peek().is_none() {
+                continue;
+            }
+
+            match prev_end {
+                None => {}
+                // Overlapping cues saturate to a gap of zero
+                Some(prev_end) if subtitle.start - prev_end >= paragraph_gap => {
+                    f.write_str("\n\n")?
+                }
+                Some(_) => f.write_str(" ")?,
+            }
+            let end = subtitle.start + subtitle.duration;
+            prev_end = Some(prev_end.map_or(end, |prev_end| prev_end.max(end)));
+
+            if let Some(interval) = timestamp_interval {
+                let is_due = last_mark.map_or(true, |mark| subtitle.start - mark >= interval);
+                if is_due {
+                    write!(f, "{} ", Mark(subtitle.start))?;
+                    last_mark = Some(subtitle.start);
+                }
+            }
+
+            for (i, word) in words.enumerate() {
+                if i != 0 {
+                    f.write_str(" ")?;
+                }
+                f.write_str(word)?;
+            }
+        }
+
+        if prev_end.is_some() {
+            f.write_str("\n")?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,918 @@
+//! Conversion to and from [TTML](https://www.w3.org/TR/ttml1/) documents, including DFXP and the
+//! [IMSC1](https://www.w3.org/TR/ttml-imsc1.0.1/) Text Profile
+//!
+//! There's no XML dependency here. Importing uses a tiny tokenizer that understands just enough
+//! XML to pull the timing and text out of each `<p>`, and exporting writes a fixed IMSC1 layout
+//!
+//! ```
+//! const TTML: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+//! <tt xmlns="http://www.w3.org/ns/ttml" xmlns:tts="http://www.w3.org/ns/ttml#styling"
+//!     xmlns:ttp="http://www.w3.org/ns/ttml#parameter" ttp:tickRate="10000000">
+//!   <head>
+//!     <styling>
+//!       <style xml:id="emphasis" tts:fontStyle="italic"/>
+//!     </styling>
+//!   </head>
+//!   <body>
+//!     <div>
+//!       <p begin="00:00:01.500" end="35000000t">
+//!         <span style="emphasis">Once upon a time</span> &amp; so on<br/>
+//!         Second line
+//!       </p>
+//!     </div>
+//!   </body>
+//! </tt>
+//! "#;
+//!
+//! let subtitles = kiss_srt::ttml::from_str(TTML).unwrap();
+//! assert_eq!(
+//!     kiss_srt::to_string(&subtitles),
+//!     "1\n00:00:01,500 --> 00:00:03,500\n<i>Once upon a time</i> & so on\nSecond line\n",
+//! );
+//!
+//! // And back again
+//! let ttml = kiss_srt::ttml::to_string(&subtitles);
+//! assert!(ttml.contains(
+//!     "<p begin=\"00:00:01.500\" end=\"00:00:03.500\">\
+//!     <span tts:fontStyle=\"italic\">Once upon a time</span> &amp; so on<br/>Second line</p>"
+//! ));
+//! ```
+//!
+//! Like WebVTT, there's no equivalent for [`Position`][crate::Position]'s pixel coordinates, so
+//! positions are dropped when exporting. Layout and region information is ignored when importing
+
+use std::{convert::TryFrom, fmt, io};
+
+use crate::{
+    error::{Error, Result, Span},
+    parse::{error_at, strip_bom},
+    tags::{parse_tag, unescape, Styles, TagKind},
+    Subtitle, Timestamp,
+};
+
+/// Attempts to parse the `<p>` elements from the provided TTML or DFXP document to a [`Vec`] of
+/// [`Subtitle`]s
+///
+/// The subtitles are sorted by their starting timestamp. Times can be clock times like
+/// `00:00:01.500` or `00:00:01:12` (frames) and offsets like `1.5s`, `1500ms`, or `15000000t`
+/// (ticks) using the `ttp:frameRate`, `ttp:frameRateMultiplier`, and `ttp:tickRate` parameters
+/// from the root element. A `begin` on a `<body>` or `<div>` offsets everything within it
+///
+/// Whitespace gets collapsed like XML's default handling, `<br/>` becomes a new line, and italic,
+/// bold, and underline styling (either inline or referenced from a `<style>`) becomes the SRT `<i>`,
+/// `<b>`, and `<u>` tags. Character references get unescaped, except for an escaped tag like
+/// `&lt;i&gt;` which keeps its `&lt;` since it would turn into real formatting in SRT otherwise
+pub fn from_str(ttml: &str) -> Result<Vec<Subtitle>> {
+    let ttml = strip_bom(ttml);
+
+    let mut tokens = Tokens { xml: ttml, pos: 0 };
+    let mut params = TimeParams::default();
+    let mut named_styles = Vec::new();
+    let mut open: Vec<Element<'_>> = Vec::new();
+    let mut saw_root = false;
+    let mut subtitles = Vec::new();
+    while let Some(token) = tokens.next() {
+        match token? {
+            Token::Start(tag) => {
+                let parent = open.last().copied().unwrap_or_default();
+                if open.is_empty() {
+                    if saw_root || local_name(tag.name) != "tt" {
+                        return Err(error_at(ttml, tag.name_span(), Error::invalid_ttml_root));
+                    }
+                    saw_root = true;
+                    params = TimeParams::from_root(&tag);
+                }
+
+                let styles = element_styles(parent.styles, &tag, &named_styles);
+                match local_name(tag.name) {
+                    "p" => {
+                        subtitles.push(parse_paragraph(
+                            &mut tokens,
+                            &tag,
+                            parent,
+                            styles,
+                            &named_styles,
+                            params,
+                        )?);
+                        continue;
+                    }
+                    "style" => {
+                        if let Some(id) = tag.attr("id") {
+                            // Styles are only inherited through the content, not from the
+                            // `<styling>` they're defined in
+                            let styles = element_styles(Styles::default(), &tag, &named_styles);
+                            named_styles.push((id.value, styles));
+                        }
+                    }
+                    _ => {}
+                }
+
+                if !tag.empty {
+                    let begin = match tag.attr("begin") {
+                        Some(attr) => parent.begin.saturating_add(attr.time(ttml, params)?),
+                        None => parent.begin,
+                    };
+                    open.push(Element {
+                        name: tag.name,
+                        start: tag.start,
+                        begin,
+                        styles,
+                    });
+                }
+            }
+            Token::End { name, span } => match open.pop() {
+                Some(element) if element.name == name => {}
+                _ => return Err(error_at(ttml, span, Error::invalid_xml)),
+            },
+            // Text outside of paragraphs is just formatting or metadata
+            Token::Text(_) | Token::CData(_) => {}
+        }
+    }
+
+    if let Some(element) = open.last() {
+        let span = Span::new(element.start, element.start + 1 + element.name.len());
+        return Err(error_at(ttml, span, Error::invalid_xml));
+    }
+    if !saw_root {
+        let end = Span::new(ttml.len(), ttml.len());
+        return Err(error_at(ttml, end, Error::invalid_ttml_root));
+    }
+
+    subtitles.sort_by_key(|subtitle| subtitle.start);
+    Ok(subtitles)
+}
+
+gen_renderers! {
+    /// Renders the `subtitles` to an IMSC1 TTML document with an empty `xml:lang`
+    fn to_string;
+    /// Renders the `subtitles` to an IMSC1 TTML document into a [`fmt::Write`]
+    fn to_fmt_writer;
+    /// Renders the `subtitles` to an IMSC1 TTML document into an [`io::Write`]
+    fn to_writer;
+    |subtitles| RenderOptions::new().document(subtitles)
+}
+
+/// Options for tweaking how subtitles get rendered to TTML
+///
+/// ```
+/// # use kiss_srt::{Duration, Timestamp, Subtitle};
+/// use kiss_srt::ttml::RenderOptions;
+///
+/// let subtitles = vec![Subtitle {
+///     start: Timestamp::from_millis(0),
+///     duration: Duration::from_millis(1_000),
+///     text: String::from("Bonjour"),
+///     position: None,
+///     id: None,
+/// }];
+///
+/// let ttml = RenderOptions::new().lang("fr").render(&subtitles);
+/// assert!(ttml.contains(" xml:lang=\"fr\">"));
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RenderOptions<'a> {
+    lang: &'a str,
+}
+
+impl<'a> RenderOptions<'a> {
+    /// The default options which match the behavior of [`to_string()`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the document's `xml:lang` like `en` or `pt-BR`. Empty by default
+    ///
+    /// IMSC1 requires the attribute, but an empty value is allowed when the language is unknown
+    pub fn lang(mut self, lang: &'a str) -> Self {
+        self.lang = lang;
+        self
+    }
+
+    gen_render_methods! {
+        /// Renders the `subtitles` using these options
+        fn document
+    }
+
+    fn document<'s>(&self, subtitles: &'s [Subtitle]) -> Document<'s>
+    where
+        'a: 's,
+    {
+        Document {
+            lang: self.lang,
+            subtitles,
+        }
+    }
+}
+
+// Ignores the namespace prefix, so `tt:p` is treated the same as `p`
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+fn is_xml_whitespace(c: char) -> bool {
+    c == ' ' || c == '\t' || c == '\n' || c == '\r'
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Attr<'a> {
+    name: &'a str,
+    value: &'a str,
+    // The byte offset of the value within the document
+    start: usize,
+}
+
+impl Attr<'_> {
+    fn span(&self) -> Span {
+        Span::new(self.start, self.start + self.value.len())
+    }
+
+    // The time expression in millis
+    fn time(&self, xml: &str, params: TimeParams) -> Result<u32> {
+        parse_time(self.value, params)
+            .and_then(|millis| u32::try_from(millis).ok())
+            .filter(|&millis| Timestamp::checked_from_millis(millis).is_some())
+            .ok_or_else(|| error_at(xml, self.span(), Error::invalid_ttml_time))
+    }
+}
+
+#[derive(Debug)]
+struct StartTag<'a> {
+    name: &'a str,
+    attrs: Vec<Attr<'a>>,
+    // Whether it's self-closing like `<br/>`
+    empty: bool,
+    // The byte offset of the `<`
+    start: usize,
+}
+
+impl<'a> StartTag<'a> {
+    fn name_span(&self) -> Span {
+        Span::new(self.start, self.start + 1 + self.name.len())
+    }
+
+    fn attr(&self, name: &str) -> Option<Attr<'a>> {
+        self.attrs
+            .iter()
+            .find(|attr| local_name(attr.name) == name)
+            .copied()
+    }
+}
+
+#[derive(Debug)]
+enum Token<'a> {
+    Start(StartTag<'a>),
+    End { name: &'a str, span: Span },
+    // Text that still has its character references escaped
+    Text(&'a str),
+    CData(&'a str),
+}
+
+// Splits the document into tags and text while skipping comments, processing instructions, and
+// doctypes
+struct Tokens<'a> {
+    xml: &'a str,
+    pos: usize,
+}
+
+impl<'a> Tokens<'a> {
+    fn error(&self, start: usize, len: usize) -> Error {
+        let end = (start + len).min(self.xml.len());
+        error_at(self.xml, Span::new(start, end), Error::invalid_xml)
+    }
+
+    // Skips past `terminator`, erroring if it never shows up
+    fn skip_past(&mut self, start: usize, terminator: &str) -> Result<&'a str> {
+        match self.xml[self.pos..].find(terminator) {
+            Some(i) => {
+                let skipped = &self.xml[self.pos..self.pos + i];
+                self.pos += i + terminator.len();
+                Ok(skipped)
+            }
+            None => Err(self.error(start, self.xml.len() - start)),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.xml[self.pos..];
+        self.pos += rest.len() - rest.trim_start_matches(is_xml_whitespace).len();
+    }
+
+    fn name(&mut self) -> &'a str {
+        let rest = &self.xml[self.pos..];
+        let len = rest
+            .find(|c| is_xml_whitespace(c) || c == '/' || c == '>' || c == '=')
+            .unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    fn start_tag(&mut self, start: usize) -> Result<StartTag<'a>> {
+        self.pos += 1;
+        let name = self.name();
+        if name.is_empty() {
+            return Err(self.error(start, 1));
+        }
+
+        let mut attrs = Vec::new();
+        loop {
+            self.skip_whitespace();
+            let rest = &self.xml[self.pos..];
+            if rest.starts_with("/>") {
+                self.pos += 2;
+                return Ok(StartTag {
+                    name,
+                    attrs,
+                    empty: true,
+                    start,
+                });
+            } else if rest.starts_with('>') {
+                self.pos += 1;
+                return Ok(StartTag {
+                    name,
+                    attrs,
+                    empty: false,
+                    start,
+                });
+            }
+
+            // Of the form `name="value"` or `name='value'`
+            let attr_start = self.pos;
+            let attr_name = self.name();
+            self.skip_whitespace();
+            let has_eq = self.xml[self.pos..].starts_with('=');
+            if attr_name.is_empty() || !has_eq {
+                return Err(self.error(attr_start, attr_name.len().max(1)));
+            }
+            self.pos += 1;
+            self.skip_whitespace();
+            let quote = match self.xml[self.pos..].chars().next() {
+                Some(quote @ '"') | Some(quote @ '\'') => quote,
+                c => return Err(self.error(self.pos, c.map_or(0, char::len_utf8))),
+            };
+            self.pos += 1;
+            let value_start = self.pos;
+            match self.xml[self.pos..].find(quote) {
+                Some(len) => {
+                    attrs.push(Attr {
+                        name: attr_name,
+                        value: &self.xml[value_start..value_start + len],
+                        start: value_start,
+                    });
+                    self.pos += len + 1;
+                }
+                None => return Err(self.error(value_start - 1, 1)),
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Result<Token<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let start = self.pos;
+            let rest = &self.xml[start..];
+            if rest.is_empty() {
+                return None;
+            }
+
+            let token = if rest.starts_with("<!--") {
+                self.pos += 4;
+                self.skip_past(start, "-->").map(|_| None)
+            } else if rest.starts_with("<![CDATA[") {
+                self.pos += 9;
+                self.skip_past(start, "]]>")
+                    .map(|text| Some(Token::CData(text)))
+            } else if rest.starts_with("<?") {
+                self.pos += 2;
+                self.skip_past(start, "?>").map(|_| None)
+            } else if rest.starts_with("<!") {
+                self.pos += 2;
+                self.skip_past(start, ">").map(|_| None)
+            } else if rest.starts_with("</") {
+                self.pos += 2;
+                self.skip_past(start, ">").map(|name| {
+                    Some(Token::End {
+                        name: name.trim_end_matches(is_xml_whitespace),
+                        span: Span::new(start, self.pos),
+                    })
+                })
+            } else if rest.starts_with('<') {
+                self.start_tag(start).map(|tag| Some(Token::Start(tag)))
+            } else {
+                let len = rest.find('<').unwrap_or(rest.len());
+                self.pos += len;
+                Ok(Some(Token::Text(&rest[..len])))
+            };
+
+            match token {
+                Ok(Some(token)) => return Some(Ok(token)),
+                Ok(None) => continue,
+                Err(err) => {
+                    // Nothing after malformed XML can be trusted
+                    self.pos = self.xml.len();
+                    return Some(Err(err));
+                }
+            }
+        }
+    }
+}
+
+// An open element along with what its children inherit
+#[derive(Clone, Copy, Debug, Default)]
+struct Element<'a> {
+    name: &'a str,
+    start: usize,
+    // The absolute time that the element's children are relative to
+    begin: u32,
+    styles: Styles,
+}
+
+// Resolves the styles from the `style` references followed by any inline styling
+fn element_styles(inherited: Styles, tag: &StartTag<'_>, named: &[(&str, Styles)]) -> Styles {
+    let mut styles = inherited;
+    if let Some(refs) = tag.attr("style") {
+        for id in refs.value.split(is_xml_whitespace) {
+            if let Some((_, named)) = named.iter().find(|(name, _)| *name == id) {
+                styles.union(*named);
+            }
+        }
+    }
+
+    for attr in &tag.attrs {
+        let value = attr.value.trim();
+        match local_name(attr.name) {
+            "fontStyle" => styles.italic = value == "italic" || value == "oblique",
+            "fontWeight" => styles.bold = value == "bold",
+            "textDecoration" => {
+                for decoration in value.split(is_xml_whitespace) {
+                    match decoration {
+                        "underline" => styles.underline = true,
+                        "noUnderline" | "none" => styles.underline = false,
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    styles
+}
+
+fn parse_paragraph(
+    tokens: &mut Tokens<'_>,
+    tag: &StartTag<'_>,
+    parent: Element<'_>,
+    styles: Styles,
+    named_styles: &[(&str, Styles)],
+    params: TimeParams,
+) -> Result<Subtitle> {
+    let xml = tokens.xml;
+    let start = match tag.attr("begin") {
+        Some(attr) => parent.begin.saturating_add(attr.time(xml, params)?),
+        None => parent.begin,
+    };
+    let (end, end_span) = match (tag.attr("end"), tag.attr("dur")) {
+        (Some(attr), _) => (
+            parent.begin.saturating_add(attr.time(xml, params)?),
+            attr.span(),
+        ),
+        (None, Some(attr)) => (start.saturating_add(attr.time(xml, params)?), attr.span()),
+        (None, None) => return Err(error_at(xml, tag.name_span(), Error::missing_ttml_timing)),
+    };
+    let start = Timestamp::from_millis(start);
+    let end = Timestamp::from_millis(end);
+    if end < start {
+        return Err(error_at(xml, end_span, Error::ts_end_before_start));
+    }
+
+    let mut text = TextBuilder::default();
+    if !tag.empty {
+        // The elements nested within the paragraph along with their styles
+        let mut open: Vec<(&str, Styles)> = Vec::new();
+        loop {
+            let current = open.last().map_or(styles, |&(_, styles)| styles);
+            match tokens.next() {
+                Some(token) => match token? {
+                    Token::Start(inner) => {
+                        if local_name(inner.name) == "br" {
+                            text.line_break();
+                        }
+                        if !inner.empty {
+                            let styles = element_styles(current, &inner, named_styles);
+                            open.push((inner.name, styles));
+                        }
+                    }
+                    Token::End { name, span } => match open.pop() {
+                        Some((open_name, _)) if open_name == name => {}
+                        None if name == tag.name => break,
+                        _ => return Err(error_at(xml, span, Error::invalid_xml)),
+                    },
+                    Token::Text(raw) => text.push(&unescape(raw), current),
+                    Token::CData(raw) => text.push(raw, current),
+                },
+                None => return Err(error_at(xml, tag.name_span(), Error::invalid_xml)),
+            }
+        }
+    }
+
+    Ok(Subtitle {
+        start,
+        duration: end - start,
+        text: text.finish(),
+        position: None,
+        id: None,
+    })
+}
+
+// Collapses whitespace and tracks the styled runs of text on each line
+#[derive(Default)]
+struct TextBuilder {
+    text: String,
+    line: Vec<(Styles, String)>,
+    space: bool,
+}
+
+impl TextBuilder {
+    fn push(&mut self, content: &str, styles: Styles) {
+        for c in content.chars() {
+            if is_xml_whitespace(c) {
+                self.space = true;
+                continue;
+            }
+
+            // Runs only get added along with text, so there's no leading whitespace. The space
+            // goes in whichever run has fewer styles like `<i>a</i> b` or `a <i>b</i>`
+            let mut pad = self.space && !self.line.is_empty();
+            self.space = false;
+            match self.line.last_mut() {
+                Some((last, run)) if *last == styles => {
+                    if pad {
+                        run.push(' ');
+                        pad = false;
+                    }
+                }
+                Some((last, run)) => {
+                    let mut widened = styles;
+                    widened.union(*last);
+                    if pad && widened == styles {
+                        run.push(' ');
+                        pad = false;
+                    }
+                    self.line.push((styles, String::new()));
+                }
+                None => self.line.push((styles, String::new())),
+            }
+            let run = &mut self.line.last_mut().expect("Just pushed").1;
+            if pad {
+                run.push(' ');
+            }
+            run.push(c);
+        }
+    }
+
+    // Lines that are only whitespace get dropped since they'd end the cue early
+    fn line_break(&mut self) {
+        self.space = false;
+        if self.line.is_empty() {
+            return;
+        }
+
+        if !self.text.is_empty() {
+            self.text.push('\n');
+        }
+        // Tags stay open across runs that share them, so `<i>a <b>b</b></i>` comes out the same
+        let mut open: Vec<&str> = Vec::new();
+        for (styles, run) in self.line.drain(..) {
+            let tags = styles.tags();
+            let keep = open
+                .iter()
+                .position(|tag| !tags.contains(tag))
+                .unwrap_or(open.len());
+            for tag in open.drain(keep..).rev() {
+                self.text.push_str("</");
+                self.text.push_str(tag);
+                self.text.push('>');
+            }
+            for tag in tags {
+                if !open.contains(&tag) {
+                    self.text.push('<');
+                    self.text.push_str(tag);
+                    self.text.push('>');
+                    open.push(tag);
+                }
+            }
+            self.text.push_str(&run);
+        }
+        for tag in open.iter().rev() {
+            self.text.push_str("</");
+            self.text.push_str(tag);
+            self.text.push('>');
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.line_break();
+        self.text
+    }
+}
+
+// The parameters from the root element that frame and tick times depend on
+#[derive(Clone, Copy, Debug)]
+struct TimeParams {
+    frame_rate: u64,
+    // Of the form `1000 1001`
+    frame_rate_multiplier: (u64, u64),
+    tick_rate: u64,
+}
+
+impl Default for TimeParams {
+    fn default() -> Self {
+        Self {
+            frame_rate: 30,
+            frame_rate_multiplier: (1, 1),
+            tick_rate: 1,
+        }
+    }
+}
+
+impl TimeParams {
+    // Invalid parameters fall back to their defaults
+    fn from_root(tag: &StartTag<'_>) -> Self {
+        let param = |name| {
+            tag.attr(name)
+                .and_then(|attr| attr.value.trim().parse::<u64>().ok())
+                .filter(|&value| value != 0)
+        };
+
+        let mut params = Self::default();
+        let frame_rate = param("frameRate");
+        let sub_frame_rate = param("subFrameRate").unwrap_or(1);
+        params.frame_rate = frame_rate.unwrap_or(params.frame_rate);
+        if let Some(attr) = tag.attr("frameRateMultiplier") {
+            let mut parts = attr.value.split_whitespace().map(str::parse::<u64>);
+            if let (Some(Ok(num)), Some(Ok(den)), None) = (parts.next(), parts.next(), parts.next())
+            {
+                if num != 0 && den != 0 {
+                    params.frame_rate_multiplier = (num, den);
+                }
+            }
+        }
+        // Without an explicit tick rate the ticks are sub-frames when there's a frame rate
+        params.tick_rate = param("tickRate")
+            .or_else(|| frame_rate.map(|frame_rate| frame_rate * sub_frame_rate))
+            .unwrap_or(params.tick_rate);
+
+        params
+    }
+
+    // The (numerator, denominator) of a frame's length in millis
+    fn frame_millis(&self) -> (u128, u128) {
+        let (num, den) = self.frame_rate_multiplier;
+        (
+            1_000 * u128::from(den),
+            u128::from(self.frame_rate) * u128::from(num),
+        )
+    }
+}
+
+// Of the form '12' or '12.345' as a (numerator, denominator)
+fn parse_decimal(text: &str) -> Option<(u128, u128)> {
+    let (int, frac) = match text.find('.') {
+        Some(i) => (&text[..i], &text[i + 1..]),
+        None => (text, ""),
+    };
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    let has_point = text.contains('.');
+    // Bounded so that the math below can't overflow
+    if int.is_empty() || !is_digits(int) || !is_digits(frac) || (has_point && frac.is_empty()) {
+        return None;
+    }
+    if int.len() + frac.len() > 18 {
+        return None;
+    }
+
+    let num = format!("{}{}", int, frac).parse().ok()?;
+    Some((num, 10u128.pow(frac.len() as u32)))
+}
+
+// Rounds `(num / den) * (unit_num / unit_den)` millis
+fn scale((num, den): (u128, u128), (unit_num, unit_den): (u128, u128)) -> u64 {
+    let den = den.saturating_mul(unit_den);
+    let millis = num.saturating_mul(unit_num).saturating_add(den / 2) / den;
+    u64::try_from(millis).unwrap_or(std::u64::MAX)
+}
+
+// Of the form '01:23:45.678', '01:23:45:12' (frames), or '12.5s'
+fn parse_time(value: &str, params: TimeParams) -> Option<u64> {
+    let value = value.trim();
+    let parts: Vec<_> = value.split(':').collect();
+    match parts.as_slice() {
+        [hours, minutes, seconds] | [hours, minutes, seconds, _] => {
+            let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+            let is_two_digits = |s: &str| s.len() == 2 && is_digits(s);
+            // Fractional seconds and sub-frames both still need the whole part before the `.`
+            let whole = |s: &'_ str| s.split('.').next().unwrap_or(s).len();
+            if hours.len() < 2 || !is_digits(hours) || !is_two_digits(minutes) {
+                return None;
+            }
+            if !is_two_digits(&seconds[..whole(seconds)]) {
+                return None;
+            }
+
+            let (seconds, frames) = match parts.get(3) {
+                Some(frames) => {
+                    // Sub-frames are too small to matter
+                    let (frames, sub_frames) = frames.split_at(whole(frames));
+                    if !is_digits(frames) || !(sub_frames.is_empty() || is_digits(&sub_frames[1..]))
+                    {
+                        return None;
+                    }
+                    let frames: u128 = frames.parse().ok()?;
+                    (
+                        (seconds.parse().ok()?, 1),
+                        scale((frames, 1), params.frame_millis()),
+                    )
+                }
+                None => (parse_decimal(seconds)?, 0),
+            };
+            let minutes: u64 = minutes.parse().ok()?;
+            if minutes >= 60 || seconds.0 / seconds.1 >= 60 {
+                return None;
+            }
+
+            let hours: u64 = hours.parse().ok()?;
+            Some(
+                hours
+                    .saturating_mul(3_600_000)
+                    .saturating_add(minutes * 60_000 + scale(seconds, (1_000, 1)))
+                    .saturating_add(frames),
+            )
+        }
+        [offset] => {
+            let metric_start = offset
+                .find(|c: char| !c.is_ascii_digit() && c != '.')
+                .unwrap_or(offset.len());
+            let count = parse_decimal(&offset[..metric_start])?;
+            let unit = match &offset[metric_start..] {
+                "h" => (3_600_000, 1),
+                "m" => (60_000, 1),
+                "s" => (1_000, 1),
+                "ms" => (1, 1),
+                "f" => params.frame_millis(),
+                "t" => (1_000, u128::from(params.tick_rate)),
+                _ => return None,
+            };
+            Some(scale(count, unit))
+        }
+        _ => None,
+    }
+}
+
+// A TTML clock time which uses a `.` before the millis
+struct TtmlTimestamp(Timestamp);
+
+impl fmt::Display for TtmlTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self(ts) = self;
+        write!(
+            f,
+            "{:02}:{:02}:{:02}.{:03}",
+            ts.hours(),
+            ts.minutes(),
+            ts.seconds(),
+            ts.millis()
+        )
+    }
+}
+
+struct Document<'a> {
+    lang: &'a str,
+    subtitles: &'a [Subtitle],
+}
+
+impl fmt::Display for Document<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(
+            "\
+<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<tt xmlns=\"http://www.w3.org/ns/ttml\" \
+xmlns:ttp=\"http://www.w3.org/ns/ttml#parameter\" \
+xmlns:tts=\"http://www.w3.org/ns/ttml#styling\" \
+ttp:profile=\"http://www.w3.org/ns/ttml/profile/imsc1/text\" xml:lang=\"",
+        )?;
+        write_escaped(f, self.lang)?;
+        f.write_str(
+            "\">
+  <head>
+    <layout>
+      <region xml:id=\"bottom\" tts:origin=\"10% 10%\" tts:extent=\"80% 80%\" \
+tts:displayAlign=\"after\" tts:textAlign=\"center\"/>
+    </layout>
+  </head>
+  <body region=\"bottom\">
+    <div>
+",
+        )?;
+
+        for subtitle in self.subtitles {
+            write!(
+                f,
+                "      <p begin=\"{}\" end=\"{}\">",
+                TtmlTimestamp(subtitle.start),
+                TtmlTimestamp(subtitle.start + subtitle.duration)
+            )?;
+            write_text(f, &subtitle.text)?;
+            f.write_str("</p>\n")?;
+        }
+
+        f.write_str(
+            "    </div>
+  </body>
+</tt>
+",
+        )
+    }
+}
+
+fn write_escaped(f: &mut fmt::Formatter<'_>, text: &str) -> fmt::Result {
+    let mut rest = text;
+    while let Some(i) = rest.find(|c| c == '&' || c == '<' || c == '>' || c == '"') {
+        f.write_str(&rest[..i])?;
+        f.write_str(match rest.as_bytes()[i] {
+            b'&' => "&amp;",
+            b'<' => "&lt;",
+            b'>' => "&gt;",
+            _ => "&quot;",
+        })?;
+        rest = &rest[i + 1..];
+    }
+
+    f.write_str(rest)
+}
+
+// Converts line breaks and tags to `<br/>`s and styled `<span>`s
+//
+// SRT tags don't have to nest properly, so spans get closed and reopened as needed to keep the
+// XML well-formed. `<font>` gets dropped since IMSC1 colors are a whole other can of worms
+fn write_text(f: &mut fmt::Formatter<'_>, text: &str) -> fmt::Result {
+    let span = |kind| match kind {
+        TagKind::Italic => "<span tts:fontStyle=\"italic\">",
+        TagKind::Bold => "<span tts:fontWeight=\"bold\">",
+        _ => "<span tts:textDecoration=\"underline\">",
+    };
+
+    // The kinds of the open spans from the outermost in
+    let mut open: Vec<TagKind> = Vec::new();
+    let mut rest = text;
+    while let Some(i) = rest.find(|c| c == '\n' || c == '<') {
+        write_escaped(f, &rest[..i])?;
+        rest = &rest[i..];
+
+        if rest.starts_with('\n') {
+            f.write_str("<br/>")?;
+            rest = &rest[1..];
+            continue;
+        }
+
+        let tag = match parse_tag(rest) {
+            Some(tag) => tag,
+            None => {
+                f.write_str("&lt;")?;
+                rest = &rest[1..];
+                continue;
+            }
+        };
+        rest = &rest[tag.len..];
+        if tag.kind == TagKind::Font {
+            continue;
+        }
+
+        match open.iter().rposition(|&kind| kind == tag.kind) {
+            Some(pos) if tag.closing => {
+                for _ in pos..open.len() {
+                    f.write_str("</span>")?;
+                }
+                open.remove(pos);
+                for &kind in &open[pos..] {
+                    f.write_str(span(kind))?;
+                }
+            }
+            None if !tag.closing => {
+                f.write_str(span(tag.kind))?;
+                open.push(tag.kind);
+            }
+            // Either a stray closing tag or a tag that's already open
+            _ => {}
+        }
+    }
+
+    write_escaped(f, rest)?;
+    for _ in &open {
+        f.write_str("</span>")?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,447 @@
+//! Conversion to and from the [WebVTT](https://www.w3.org/TR/webvtt1/) format
+//!
+//! ```
+//! const VTT: &str = "\
+//! WEBVTT - Some title
+//!
+//! NOTE Comments, styles, and regions get skipped
+//!
+//! intro
+//! 00:01.000 --> 00:02.500 align:start line:0
+//! <v Narrator><i>Once upon a time</i> &amp; so on
+//! ";
+//!
+//! let subtitles = kiss_srt::webvtt::from_str(VTT).unwrap();
+//! assert_eq!(
+//!     kiss_srt::to_string(&subtitles),
+//!     "1\n00:00:01,000 --> 00:00:02,500\n<i>Once upon a time</i> & so on\n",
+//! );
+//! ```
+//!
+//! ```
+//! # use kiss_srt::{Duration, Timestamp, Subtitle};
+//! let subtitles = vec![
+//!     Subtitle {
+//!         start: Timestamp::from_millis(1_500),
+//!         duration: Duration::from_millis(2_000),
+//!         text: String::from("<i>Fish & chips</i>\n<font color=\"red\">-></font>"),
+//!         position: None,
+//!         id: None,
+//!     },
+//! ];
+//!
+//! const VTT: &str = "\
+//! WEBVTT
+//!
+//! 00:00:01.500 --> 00:00:03.500
+//! <i>Fish &amp; chips</i>
+//! -&gt;
+//! ";
+//! assert_eq!(kiss_srt::webvtt::to_string(&subtitles), VTT);
+//! ```
+//!
+//! WebVTT has no equivalent for [`Position`][crate::Position]'s pixel coordinates, so positions
+//! are dropped when exporting. Likewise cue settings are dropped when importing
+
+use std::{fmt, io};
+
+use crate::{
+    error::{Error, Result, Span, TimestampPart},
+    parse::{parse_ascii_num, parse_separator, strip_bom, Cursor, TsResult},
+    tags::{keep_escaped_tags, parse_tag, TagKind},
+    Subtitle, Timestamp,
+};
+
+/// Attempts to parse the provided WebVTT text to a [`Vec`] of [`Subtitle`]s
+///
+/// `NOTE`, `STYLE`, and `REGION` blocks are skipped along with cue identifiers and settings. `<i>`,
+/// `<b>`, and `<u>` tags are kept as is while all other tags are stripped and character references
+/// like `&amp;` are unescaped. An escaped tag like `&lt;i&gt;` keeps its `&lt;` since it would
+/// turn into real formatting in SRT otherwise
+pub fn from_str(vtt: &str) -> Result<Vec<Subtitle>> {
+    let vtt = strip_bom(vtt);
+    let mut lines = (1..).zip(vtt.lines());
+
+    let header = lines.next().map_or("", |(_, line)| line);
+    if !is_block(header, "WEBVTT") {
+        return Err(Error::invalid_vtt_header(1, Span::new(0, header.len())));
+    }
+    skip_block(&mut lines);
+
+    let mut subtitles = Vec::new();
+    while let Some((line_num, line)) = lines.find(|(_, line)| !line.is_empty()) {
+        if is_block(line, "NOTE") || is_block(line, "STYLE") || is_block(line, "REGION") {
+            skip_block(&mut lines);
+            continue;
+        }
+
+        // Anything before the timing line is the cue's identifier
+        let (line_num, line) = if line.contains("-->") {
+            (line_num, line)
+        } else {
+            match lines.next() {
+                Some((line_num, line)) if line.contains("-->") => (line_num, line),
+                Some((line_num, line)) => {
+                    return Err(Error::missing_cue_timing(
+                        line_num,
+                        Span::new(0, line.len()),
+                    ))
+                }
+                None => return Err(Error::missing_cue_timing(line_num + 1, Span::default())),
+            }
+        };
+        let (start, end) = parse_timing_line(line_num, line)?;
+
+        let mut text = String::new();
+        for (_, line) in lines.by_ref() {
+            if line.is_empty() {
+                break;
+            }
+
+            // Lines that were only tags would otherwise leave empty lines behind
+            let len = text.len();
+            if len != 0 {
+                text.push('\n');
+            }
+            let line_start = text.len();
+            push_text(&mut text, line);
+            if text.len() == line_start {
+                text.truncate(len);
+            }
+        }
+
+        subtitles.push(Subtitle {
+            start,
+            duration: end - start,
+            text,
+            position: None,
+            id: None,
+        });
+    }
+
+    Ok(subtitles)
+}
+
+// Blocks start with a keyword followed by the end of the line, a space, or a tab
+fn is_block(line: &str, keyword: &str) -> bool {
+    line.starts_with(keyword)
+        && match line.as_bytes().get(keyword.len()) {
+            None | Some(b' ') | Some(b'\t') => true,
+            Some(_) => false,
+        }
+}
+
+fn skip_block<'a>(lines: &mut impl Iterator<Item = (usize, &'a str)>) {
+    for (_, line) in lines {
+        if line.is_empty() {
+            break;
+        }
+    }
+}
+
+// Of the form '01:23:45.678' or '23:45.678'
+pub(crate) fn parse_ts(cursor: &mut Cursor<'_>) -> TsResult<Timestamp> {
+    let first_start = cursor.pos;
+    let (first, _) = parse_ascii_num(cursor, 2, 2, 100, TimestampPart::Hours)?;
+    parse_separator(cursor, b':', TimestampPart::HoursSeparator)?;
+    let (second, _) = parse_ascii_num(cursor, 2, 2, 60, TimestampPart::Minutes)?;
+    let (hours, minutes, seconds) = if cursor.eat(b":") {
+        let (seconds, _) = parse_ascii_num(cursor, 2, 2, 60, TimestampPart::Seconds)?;
+        (first, second, seconds)
+    } else if first >= 60 {
+        // Without the hours the first part is really the minutes
+        return Err((
+            Span::new(first_start, first_start + 2),
+            TimestampPart::Minutes,
+        ));
+    } else {
+        (0, first, second)
+    };
+    parse_separator(cursor, b'.', TimestampPart::SecondsSeparator)?;
+    let (millis, _) = parse_ascii_num(cursor, 3, 3, 1_000, TimestampPart::Millis)?;
+
+    // All of the components were already range checked
+    Ok(
+        Timestamp::new(hours as u8, minutes as u8, seconds as u8, millis)
+            .expect("Components are in range"),
+    )
+}
+
+fn eat_whitespace(cursor: &mut Cursor<'_>) -> bool {
+    let start = cursor.pos;
+    while cursor.eat(b" ") || cursor.eat(b"\t") {}
+    cursor.pos != start
+}
+
+// Of the form '01:23:45.678 --> 01:23:45.678 <settings>'
+fn parse_timing_line(line_num: usize, line: &str) -> Result<(Timestamp, Timestamp)> {
+    let mut cursor = Cursor::new(line);
+    let start = parse_ts(&mut cursor)
+        .map_err(|(span, part)| Error::invalid_vtt_ts(line_num, span, part))?;
+
+    let divider_start = cursor.pos;
+    if !(eat_whitespace(&mut cursor) && cursor.eat(b"-->") && eat_whitespace(&mut cursor)) {
+        cursor.pos = divider_start;
+        return Err(Error::invalid_ts_divider(line_num, cursor.span(5)));
+    }
+
+    let end_start = cursor.pos;
+    let end = parse_ts(&mut cursor)
+        .map_err(|(span, part)| Error::invalid_vtt_ts(line_num, span, part))?;
+    if end < start {
+        return Err(Error::ts_end_before_start(
+            line_num,
+            Span::new(end_start, cursor.pos),
+        ));
+    }
+    // Anything else has to be cue settings, which get ignored
+    if !cursor.is_empty() && !eat_whitespace(&mut cursor) {
+        return Err(Error::invalid_vtt_ts(
+            line_num,
+            cursor.rest(),
+            TimestampPart::Millis,
+        ));
+    }
+
+    Ok((start, end))
+}
+
+// Unescapes character references and strips all tags besides `<i>`, `<b>`, and `<u>`
+fn push_text(text: &mut String, line: &str) {
+    let mut escaped_lts = Vec::new();
+    let mut rest = line;
+    while let Some(i) = rest.find(|c| c == '&' || c == '<') {
+        text.push_str(&rest[..i]);
+        rest = &rest[i..];
+
+        if rest.starts_with('<') {
+            let end = rest.find('>').unwrap_or(rest.len());
+            let tag = &rest[1..end];
+            let (closing, name) = if tag.starts_with('/') {
+                ("/", &tag[1..])
+            } else {
+                ("", tag)
+            };
+            // Tags can have classes and annotations like `<i.loud>` or `<v Bob>`
+            let name = name.split(|c| c == '.' || c == ' ' || c == '\t').next();
+            if let Some(name @ "i") | Some(name @ "b") | Some(name @ "u") = name {
+                text.push('<');
+                text.push_str(closing);
+                text.push_str(name);
+                text.push('>');
+            }
+            rest = rest.get(end + 1..).unwrap_or_default();
+        } else {
+            let reference = rest.find(';').map(|end| (&rest[1..end], end + 1));
+            let unescaped = reference.and_then(|(name, len)| {
+                let c = match name {
+                    "amp" => '&',
+                    "lt" => '<',
+                    "gt" => '>',
+                    "nbsp" => '\u{a0}',
+                    "lrm" => '\u{200e}',
+                    "rlm" => '\u{200f}',
+                    _ => return None,
+                };
+                Some((c, len))
+            });
+            match unescaped {
+                Some((c, len)) => {
+                    if c == '<' {
+                        escaped_lts.push(text.len());
+                    }
+                    text.push(c);
+                    rest = &rest[len..];
+                }
+                None => {
+                    text.push('&');
+                    rest = &rest[1..];
+                }
+            }
+        }
+    }
+
+    text.push_str(rest);
+    keep_escaped_tags(text, &escaped_lts);
+}
+
+gen_renderers! {
+    /// Renders the `subtitles` to WebVTT without any cue identifiers
+    fn to_string;
+    /// Renders the `subtitles` to WebVTT into a [`fmt::Write`]
+    fn to_fmt_writer;
+    /// Renders the `subtitles` to WebVTT into an [`io::Write`]
+    fn to_writer;
+    |subtitles| RenderOptions::new().vtt(subtitles)
+}
+
+/// Options for tweaking how subtitles get rendered to WebVTT
+///
+/// ```
+/// # use kiss_srt::{Duration, Timestamp, Subtitle};
+/// use kiss_srt::webvtt::RenderOptions;
+///
+/// let subtitles = vec![
+///     Subtitle {
+///         start: Timestamp::from_millis(0),
+///         duration: Duration::from_millis(1_000),
+///         text: String::from("Inferred ID"),
+///         position: None,
+///         id: None,
+///     },
+///     Subtitle {
+///         start: Timestamp::from_millis(1_000),
+///         duration: Duration::from_millis(1_000),
+///         text: String::from("Explicit ID"),
+///         position: None,
+///         id: Some(10),
+///     },
+/// ];
+///
+/// const VTT: &str = "\
+/// WEBVTT
+///
+/// 1
+/// 00:00:00.000 --> 00:00:01.000
+/// Inferred ID
+///
+/// 10
+/// 00:00:01.000 --> 00:00:02.000
+/// Explicit ID
+/// ";
+/// assert_eq!(RenderOptions::new().cue_ids(true).render(&subtitles), VTT);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RenderOptions {
+    cue_ids: bool,
+}
+
+impl RenderOptions {
+    /// The default options which match the behavior of [`to_string()`]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether each cue gets an identifier. Off by default
+    ///
+    /// The identifiers follow the same rules as SRT IDs, so they're inferred as index + 1 unless an
+    /// explicit [`Subtitle::id`] is set
+    pub fn cue_ids(mut self, cue_ids: bool) -> Self {
+        self.cue_ids = cue_ids;
+        self
+    }
+
+    gen_render_methods! {
+        /// Renders the `subtitles` using these options
+        fn vtt
+    }
+
+    fn vtt<'a>(&'a self, subtitles: &'a [Subtitle]) -> Vtt<'a> {
+        Vtt {
+            options: self,
+            subtitles,
+        }
+    }
+
+    fn cue<'a>(&self, id: u32, subtitle: &'a Subtitle) -> Cue<'a> {
+        Cue {
+            id: if self.cue_ids { Some(id) } else { None },
+            subtitle,
+        }
+    }
+}
+
+// A WebVTT timestamp which uses a `.` before the millis
+pub(crate) struct VttTimestamp(pub(crate) Timestamp);
+
+impl fmt::Display for VttTimestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self(ts) = self;
+        write!(
+            f,
+            "{:02}:{:02}:{:02}.{:03}",
+            ts.hours(),
+            ts.minutes(),
+            ts.seconds(),
+            ts.millis()
+        )
+    }
+}
+
+// The whole WebVTT file with its header
+struct Vtt<'a> {
+    options: &'a RenderOptions,
+    subtitles: &'a [Subtitle],
+}
+
+impl fmt::Display for Vtt<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { options, subtitles } = self;
+
+        f.write_str("WEBVTT\n")?;
+        for (i, subtitle) in (1..).zip(subtitles.iter()) {
+            let id = subtitle.id.unwrap_or(i);
+            write!(f, "{}", options.cue(id, subtitle))?;
+        }
+
+        Ok(())
+    }
+}
+
+// A single cue preceded by the blank line that separates it from whatever came before
+struct Cue<'a> {
+    id: Option<u32>,
+    subtitle: &'a Subtitle,
+}
+
+impl fmt::Display for Cue<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Self { id, subtitle } = self;
+
+        f.write_str("\n")?;
+        if let Some(id) = id {
+            writeln!(f, "{}", id)?;
+        }
+        let end = subtitle.start + subtitle.duration;
+        writeln!(
+            f,
+            "{} --> {}",
+            VttTimestamp(subtitle.start),
+            VttTimestamp(end)
+        )?;
+        write_text(f, &subtitle.text)?;
+        f.write_str("\n")
+    }
+}
+
+// Escapes the text while passing through the tags that WebVTT shares with SRT
+fn write_text(f: &mut fmt::Formatter<'_>, text: &str) -> fmt::Result {
+    let mut rest = text;
+    while let Some(i) = rest.find(|c| c == '&' || c == '<' || c == '>') {
+        f.write_str(&rest[..i])?;
+        rest = &rest[i..];
+
+        // `<i>`, `<b>`, and `<u>` map directly to WebVTT. `<font>` has no equivalent, so it's
+        // dropped
+        if let Some(tag) = parse_tag(rest) {
+            let closing = if tag.closing { "/" } else { "" };
+            match tag.kind {
+                TagKind::Italic => write!(f, "<{}i>", closing)?,
+                TagKind::Bold => write!(f, "<{}b>", closing)?,
+                TagKind::Underline => write!(f, "<{}u>", closing)?,
+                TagKind::Font => {}
+            }
+            rest = &rest[tag.len..];
+            continue;
+        }
+
+        f.write_str(match rest.as_bytes()[0] {
+            b'&' => "&amp;",
+            b'<' => "&lt;",
+            _ => "&gt;",
+        })?;
+        rest = &rest[1..];
+    }
+
+    f.write_str(rest)
+}
@@ -0,0 +1,156 @@
+use kiss_srt::{ass, error::ErrorKind, Duration, Subtitle, Timestamp};
+
+macro_rules! snapshot_import_err {
+    ( $( ($fn_name:ident, $text:expr ) ),* $(,)? ) => {
+        $(
+        #[test]
+        fn $fn_name() {
+            let err = ass::from_str($text).unwrap_err();
+            ::insta::assert_snapshot!(err.to_string());
+            ::insta::assert_snapshot!(err.annotate($text).to_string());
+        }
+        )*
+    };
+}
+
+const SSA: &str = r"[Script Info]
+; Comments and styles get ignored
+ScriptType: v4.00
+
+[V4 Styles]
+Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, TertiaryColour, BackColour, Bold, Italic, BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, AlphaLevel, Encoding
+Style: Default,Arial,20,16777215,65535,65535,-2147483640,-1,0,1,3,0,2,30,30,30,0,0
+
+[Events]
+Format: Marked, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text
+Dialogue: Marked=0,0:00:05.00,0:00:06.50,Default,,0000,0000,0000,,Listed out of order
+Dialogue: Marked=0,0:00:01.25,0:00:03.00,Default,Bob,0000,0000,0000,,{\pos(10,10)\b1}Bold{\b0}, {\i1}commas{\i},\Nand {\u1\fs20}more{\u0}
+Comment: Marked=0,0:00:00.00,0:00:01.00,Default,,0000,0000,0000,,Not dialogue
+Dialogue: Marked=0,0:00:03.00,0:00:04.00,Default,,0000,0000,0000,,\NSoft\nbreak\hand {\p1}m 0 0 l 100 0 100 100{\p0}no drawing\N\N
+Dialogue: Marked=0,0:00:04.00,0:00:05.00,Default,,0000,0000,0000,,{\b700}Weighted{\b0} { not a block \ backslash
+";
+
+#[test]
+fn import() {
+    let subtitles = ass::from_str(SSA).unwrap();
+    insta::assert_snapshot!(kiss_srt::to_string(&subtitles));
+}
+
+#[test]
+fn export() {
+    let subtitles = vec![
+        Subtitle {
+            start: Timestamp::from_millis(1_004),
+            duration: Duration::from_millis(2_001),
+            text: String::from("<i>Italic</i> and <B>bold</B>\n<font color=\"red\">Red</font> <3"),
+            position: None,
+            id: None,
+        },
+        Subtitle {
+            start: Timestamp::from_millis(3_600_000),
+            duration: Duration::from_millis(995),
+            text: String::from("An hour in"),
+            position: None,
+            id: None,
+        },
+    ];
+
+    let script = ass::to_string(&subtitles);
+    insta::assert_snapshot!(script);
+
+    let mut io_rendered = Vec::new();
+    ass::to_writer(&mut io_rendered, &subtitles).unwrap();
+    assert_eq!(String::from_utf8(io_rendered).unwrap(), script);
+}
+
+#[test]
+fn round_trip() {
+    let subtitles = ass::from_str(SSA).unwrap();
+    assert_eq!(
+        ass::from_str(&ass::to_string(&subtitles)).unwrap(),
+        subtitles
+    );
+}
+
+#[test]
+fn round_trip_max_timestamp() {
+    let subtitles = vec![Subtitle {
+        start: Timestamp::MAX,
+        duration: Duration::default(),
+        text: String::from("The very end"),
+        position: None,
+        id: None,
+    }];
+    let script = ass::to_string(&subtitles);
+    assert!(script.ends_with("Dialogue: 0,99:59:59.99,99:59:59.99,Default,,0,0,0,,The very end\n"));
+
+    let imported = ass::from_str(&script).unwrap();
+    assert_eq!(imported[0].start, Timestamp::MAX - Duration::from_millis(9));
+    assert_eq!(ass::to_string(&imported), script);
+}
+
+#[test]
+fn escaping() {
+    let subtitle = |text: &str| Subtitle {
+        start: Timestamp::default(),
+        duration: Duration::default(),
+        text: String::from(text),
+        position: None,
+        id: None,
+    };
+    let round_trip = |text| {
+        let script = ass::to_string(&[subtitle(text)]);
+        ass::from_str(&script).unwrap().remove(0).text
+    };
+
+    let braces = r"{\i1} looks like a block, C:\path\{file}, {}, \{ and \}";
+    let script = ass::to_string(&[subtitle(braces)]);
+    assert!(script.ends_with(concat!(
+        r",,\{\i1\} looks like a block, C:\path\\{file\}, \{\}, \\{ and \\}",
+        "\n"
+    )));
+    assert_eq!(round_trip(braces), braces);
+
+    // There's no escaping these, so they come back as what they look like
+    assert_eq!(round_trip(r"a\Nb"), "a\nb");
+    assert_eq!(round_trip(r"a\nb"), "a b");
+    assert_eq!(round_trip(r"a\hb"), "a\u{a0}b");
+}
+
+#[test]
+fn import_error_kinds() {
+    let kind = |text| ass::from_str(text).unwrap_err().kind;
+    assert_eq!(kind(""), ErrorKind::MissingAssEvents);
+    assert_eq!(
+        kind("[Events]\nFormat: Start, End\n"),
+        ErrorKind::InvalidAssFormat
+    );
+    assert_eq!(
+        kind("[Events]\nDialogue: 0,0:00:01.00,0:00:00.00,Default,,0,0,0,,Text\n"),
+        ErrorKind::TimestampEndBeforeStart
+    );
+}
+
+const SRT: &str = "\
+1
+00:00:00,000 --> 00:00:01,000
+This is SRT
+";
+
+const MISSING_FIELDS: &str = "\
+[Events]
+Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text
+Dialogue: 0,0:00:01.00,0:00:02.00,Default
+";
+
+const MILLIS: &str = "\
+[Events]
+Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text
+Dialogue: 0,0:00:01.000,0:00:02.00,Default,,0,0,0,,Three digit fraction
+";
+
+snapshot_import_err!(
+    (missing_events, SRT),
+    (missing_fields, MISSING_FIELDS),
+    (millis, MILLIS),
+);
@@ -0,0 +1,85 @@
+use std::borrow::Cow;
+
+use kiss_srt::Subtitle;
+
+#[test]
+fn matches_from_str() {
+    const TEXT: &str = "\
+1\r
+00:00:00,000 --> 00:00:01,000 X1:1 X2:2 Y1:3 Y2:4\r
+CRLF\r
+line endings\r
+\r
+2
+00:00:01,000 --> 00:00:02,000
+LF
+line endings
+
+3
+00:00:02,000 --> 00:00:03,000
+Extra carriage returns\r\r
+\r
+4
+00:00:03,000 --> 00:00:04,000
+";
+
+    let borrowed = kiss_srt::from_str_borrowed(TEXT).unwrap();
+    let owned: Vec<Subtitle> = borrowed.into_iter().map(Subtitle::from).collect();
+    assert_eq!(owned, kiss_srt::from_str(TEXT).unwrap());
+}
+
+#[test]
+fn only_allocates_when_needed() {
+    const TEXT: &str = "\
+1\r
+00:00:00,000 --> 00:00:01,000\r
+Single CRLF line\r
+\r
+2\r
+00:00:01,000 --> 00:00:02,000\r
+Multiple\r
+CRLF lines\r
+\r
+3
+00:00:02,000 --> 00:00:03,000
+Multiple
+LF lines
+
+4
+00:00:03,000 --> 00:00:04,000
+Trailing\r\r
+carriage returns
+
+5
+00:00:04,000 --> 00:00:05,000
+";
+
+    let borrowed = kiss_srt::from_str_borrowed(TEXT).unwrap();
+    let texts: Vec<_> = borrowed
+        .iter()
+        .map(|subtitle| match &subtitle.text {
+            Cow::Borrowed(text) => (true, *text),
+            Cow::Owned(text) => (false, text.as_str()),
+        })
+        .collect();
+    assert_eq!(
+        texts,
+        [
+            (true, "Single CRLF line"),
+            (false, "Multiple\nCRLF lines"),
+            (true, "Multiple\nLF lines"),
+            (false, "Trailing\ncarriage returns"),
+            (true, ""),
+        ],
+    );
+}
+
+#[test]
+fn same_error() {
+    const BAD: &str = "1\n00:00:00,000 --> 00:00:01,000\nFine\n\nnope\n";
+
+    assert_eq!(
+        kiss_srt::from_str_borrowed(BAD).unwrap_err(),
+        kiss_srt::from_str(BAD).unwrap_err(),
+    );
+}
@@ -0,0 +1,267 @@
+use kiss_srt::{
+    csv::{self, Options, TimestampFormat},
+    error::ErrorKind,
+    Duration, Position, Subtitle, Timestamp,
+};
+
+macro_rules! snapshot_import_err {
+    ( $( ($fn_name:ident, $text:expr ) ),* $(,)? ) => {
+        $(
+        #[test]
+        fn $fn_name() {
+            let err = csv::from_str($text).unwrap_err();
+            ::insta::assert_snapshot!(err.to_string());
+            ::insta::assert_snapshot!(err.annotate($text).to_string());
+        }
+        )*
+    };
+}
+
+fn subtitles() -> Vec<Subtitle> {
+    vec![
+        Subtitle {
+            start: Timestamp::from_millis(500),
+            duration: Duration::from_millis(1_000),
+            text: String::from("Plain text"),
+            position: None,
+            id: None,
+        },
+        Subtitle {
+            start: Timestamp::from_millis(2_000),
+            duration: Duration::from_millis(1_250),
+            text: String::from("Commas, \"quotes\",\nand <i>multiple</i> lines"),
+            position: None,
+            id: Some(10),
+        },
+        Subtitle {
+            start: Timestamp::new(12, 34, 56, 789).unwrap(),
+            duration: Duration::from_millis(1),
+            text: String::from("Tabs\tand\tstuff"),
+            position: None,
+            id: None,
+        },
+    ]
+}
+
+const SHEET: &str = "\u{feff}\
+Speaker,Text,END,Start,Notes\r
+Alice,\"Quoted \"\"text\"\"\r
+\r
+over lines\",\" 00:00:02,500 \",\"00:00:01,000\",\r
+,,,,\r
+Bob,Unquoted,\"00:00:04,000\",\"00:00:03,000\",\"Has, commas\"\r
+";
+
+#[test]
+fn import() {
+    let subtitles = csv::from_str(SHEET).unwrap();
+    insta::assert_snapshot!(kiss_srt::to_string(&subtitles));
+}
+
+#[test]
+fn export() {
+    let rendered = csv::to_string(&subtitles());
+    insta::assert_snapshot!(rendered);
+
+    let mut io_rendered = Vec::new();
+    csv::to_writer(&mut io_rendered, &subtitles()).unwrap();
+    assert_eq!(String::from_utf8(io_rendered).unwrap(), rendered);
+}
+
+#[test]
+fn export_tsv() {
+    let tsv = Options::new()
+        .delimiter('\t')
+        .timestamp_format(TimestampFormat::WebVtt);
+    insta::assert_snapshot!(tsv.render(&subtitles()));
+}
+
+#[test]
+fn round_trip() {
+    let formats = [
+        TimestampFormat::Srt,
+        TimestampFormat::WebVtt,
+        TimestampFormat::Millis,
+        TimestampFormat::Seconds,
+    ];
+    // The explicit ID is only kept when asked for
+    let mut expected = subtitles();
+    expected[1].id = None;
+    for &delimiter in &[',', '\t', ';'] {
+        for &format in &formats {
+            let options = Options::new().delimiter(delimiter).timestamp_format(format);
+            let rendered = options.render(&subtitles());
+            assert_eq!(options.parse(&rendered).unwrap(), expected, "{}", rendered);
+        }
+    }
+}
+
+#[test]
+fn drops_positions() {
+    let mut subtitles = subtitles();
+    subtitles[0].position = Some(Position {
+        x1: 1,
+        x2: 2,
+        y1: 3,
+        y2: 4,
+    });
+    let rendered = csv::to_string(&subtitles);
+    subtitles[0].position = None;
+    subtitles[1].id = None;
+    assert_eq!(csv::from_str(&rendered).unwrap(), subtitles);
+}
+
+#[test]
+fn ids() {
+    const CSV: &str = "id,start,end,text\n,0,0,a\n2,0,0,b\n7,0,0,c\n4,0,0,d\n";
+    let ids = |keep| {
+        Options::new()
+            .timestamp_format(TimestampFormat::Millis)
+            .keep_ids(keep)
+            .parse(CSV)
+            .unwrap()
+            .iter()
+            .map(|subtitle| subtitle.id)
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(ids(false), [None; 4]);
+    assert_eq!(ids(true), [None, Some(2), Some(7), Some(4)]);
+
+    let rendered = csv::to_string(&subtitles());
+    let kept = Options::new().keep_ids(true).parse(&rendered).unwrap();
+    assert_eq!(kept[1].id, Some(10));
+
+    // The column is optional
+    let subtitles = Options::new()
+        .timestamp_format(TimestampFormat::Millis)
+        .parse("start,end,text\n0,0,a\n")
+        .unwrap();
+    assert_eq!(subtitles[0].id, None);
+}
+
+#[test]
+fn drops_blank_lines_in_text() {
+    let subtitles = Options::new()
+        .timestamp_format(TimestampFormat::Millis)
+        .parse("start,end,text\n0,0,\"First\n\n  \nSecond\"\n0,0,\"\n\"\n")
+        .unwrap();
+    assert_eq!(subtitles[0].text, "First\nSecond");
+    assert_eq!(subtitles[1].text, "");
+}
+
+#[test]
+fn numeric_timestamps() {
+    let parse = |format, text: &str| {
+        Options::new()
+            .timestamp_format(format)
+            .parse(&format!("start,end,text\n{},Text\n", text))
+            .map(|subtitles| {
+                let subtitle = &subtitles[0];
+                (subtitle.start, subtitle.start + subtitle.duration)
+            })
+            .map_err(|err| (err.kind, err.part))
+    };
+    let ts = Timestamp::from_millis;
+
+    assert_eq!(
+        parse(TimestampFormat::Millis, "0,1500"),
+        Ok((ts(0), ts(1_500)))
+    );
+    assert_eq!(
+        parse(TimestampFormat::Seconds, "1,2.5"),
+        Ok((ts(1_000), ts(2_500)))
+    );
+    assert_eq!(
+        parse(TimestampFormat::Seconds, "0.0004,0.0005"),
+        Ok((ts(0), ts(1)))
+    );
+    assert_eq!(
+        parse(TimestampFormat::WebVtt, "01:02.003,01:00:00.000"),
+        Ok((ts(62_003), ts(3_600_000)))
+    );
+
+    let invalid_millis = Err((
+        ErrorKind::InvalidNumericTimestamp,
+        Some(kiss_srt::error::TimestampPart::Millis),
+    ));
+    assert_eq!(parse(TimestampFormat::Millis, "1.5,2"), invalid_millis);
+    assert_eq!(parse(TimestampFormat::Millis, "-1,2"), invalid_millis);
+    assert_eq!(
+        parse(TimestampFormat::Millis, "0,99999999999"),
+        invalid_millis
+    );
+    let invalid_seconds = Err((
+        ErrorKind::InvalidNumericTimestamp,
+        Some(kiss_srt::error::TimestampPart::Seconds),
+    ));
+    assert_eq!(parse(TimestampFormat::Seconds, "1.,2"), invalid_seconds);
+    assert_eq!(parse(TimestampFormat::Seconds, ".5,2"), invalid_seconds);
+    assert_eq!(parse(TimestampFormat::Seconds, "0,1e3"), invalid_seconds);
+}
+
+#[test]
+fn import_error_kinds() {
+    let kind = |text| csv::from_str(text).unwrap_err().kind;
+    assert_eq!(kind(""), ErrorKind::MissingCsvColumn);
+    assert_eq!(kind("start,end\n"), ErrorKind::MissingCsvColumn);
+    assert_eq!(
+        kind("start,end,text\n\"00:00:02,000\",\"00:00:01,000\",Text\n"),
+        ErrorKind::TimestampEndBeforeStart
+    );
+    assert_eq!(
+        kind("start,end,text\n00:00:01.000,00:00:02.000,Text\n"),
+        ErrorKind::InvalidTimestampStart
+    );
+    assert_eq!(
+        kind("start,end,text\n\"00:00:01,000\",00:00:02.000,Text\n"),
+        ErrorKind::InvalidTimestampEnd
+    );
+    assert_eq!(
+        kind("id,start,end,text\nA1,\"00:00:01,000\",\"00:00:02,000\",Text\n"),
+        ErrorKind::InvalidId
+    );
+    assert_eq!(
+        kind("start,end,text\n\"unclosed\n"),
+        ErrorKind::UnclosedCsvQuote
+    );
+    assert_eq!(
+        kind("start,end,text\n\"00:00:01,000\"x,\"00:00:02,000\",Text\n"),
+        ErrorKind::InvalidCsvQuote
+    );
+}
+
+const MISSING_COLUMN: &str = "\
+id,start,stop,text
+1,\"00:00:01,000\",\"00:00:02,000\",Text
+";
+
+const MISSING_FIELD: &str = "\
+id,start,end,text
+1,\"00:00:01,000\",\"00:00:02,000\"
+";
+
+const UNCLOSED_QUOTE: &str = "\
+id,start,end,text
+1,\"00:00:01,000\",\"00:00:02,000\",\"Never closed
+";
+
+const TEXT_AFTER_QUOTE: &str = "\
+id,start,end,text
+1,\"00:00:01,000\",\"00:00:02,000\",\"Quoted\" and not
+";
+
+const INVALID_TIMESTAMP: &str = "\
+id,start,end,text
+1,\"00:00:01,000\",\"00:00:02,000\",First
+2,\"00:00:03,000\",\"00:00:04,000\",\"Second
+on two lines\"
+3,\"00:00:05,000\",\"00:00:06,00\",Third
+";
+
+snapshot_import_err!(
+    (missing_column, MISSING_COLUMN),
+    (missing_field, MISSING_FIELD),
+    (unclosed_quote, UNCLOSED_QUOTE),
+    (text_after_quote, TEXT_AFTER_QUOTE),
+    (invalid_timestamp, INVALID_TIMESTAMP),
+);
@@ -0,0 +1,134 @@
+use kiss_srt::{
+    error::{ErrorKind, WarningKind},
+    Duration, Subtitle, Timestamp,
+};
+
+#[test]
+fn reports_errors_across_the_whole_file() {
+    let subtitles: Vec<_> = (0..1_000)
+        .map(|i| Subtitle {
+            start: Timestamp::from_millis(i * 1_000),
+            duration: Duration::from_millis(500),
+            text: format!("Cue {}", i + 1),
+            position: None,
+            id: None,
+        })
+        .collect();
+    let mut lines: Vec<_> = kiss_srt::to_string(&subtitles)
+        .lines()
+        .map(String::from)
+        .collect();
+    // Break cues at the start, middle, and end
+    for &line_num in &[2, 1_998, 3_998] {
+        let line = &mut lines[line_num - 1];
+        *line = line.replacen(',', ".", 1);
+    }
+    let text = lines.join("\n");
+
+    let diagnostics = kiss_srt::diagnose(&text);
+    let errors: Vec<_> = diagnostics
+        .errors
+        .iter()
+        .map(|err| (err.line, err.kind))
+        .collect();
+    assert_eq!(
+        errors,
+        [
+            (2, ErrorKind::InvalidTimestampStart),
+            (1_998, ErrorKind::InvalidTimestampStart),
+            (3_998, ErrorKind::InvalidTimestampStart),
+        ]
+    );
+    assert!(diagnostics.warnings.is_empty());
+}
+
+#[test]
+fn warnings() {
+    const TEXT: &str = "\
+1
+00:00:01,000 --> 00:00:03,000
+First
+
+2
+00:00:02,000 --> 00:00:04,000
+Overlaps the first
+
+3
+00:00:00,000 --> 00:00:01,000
+Starts before the second
+
+4
+00:00:05,000 --> 00:00:06,000
+
+";
+
+    let diagnostics = kiss_srt::diagnose(TEXT);
+    assert!(diagnostics.errors.is_empty());
+    let warnings: Vec<_> = diagnostics
+        .warnings
+        .iter()
+        .map(|warning| (warning.line, warning.kind))
+        .collect();
+    assert_eq!(
+        warnings,
+        [
+            (6, WarningKind::Overlapping),
+            (10, WarningKind::OutOfOrder),
+            (14, WarningKind::EmptyText),
+        ]
+    );
+    insta::assert_snapshot!(diagnostics.warnings[0].to_string(), @"Cue overlaps with the previous cue on line 6");
+}
+
+#[test]
+fn clean_file() {
+    const TEXT: &str = "1\n00:00:00,000 --> 00:00:01,000\nAll good\n";
+    assert!(kiss_srt::diagnose(TEXT).is_empty());
+}
+
+#[test]
+fn id_warnings() {
+    const TEXT: &str = "\
+1
+00:00:00,000 --> 00:00:01,000
+Fine
+
+3
+00:00:01,000 --> 00:00:02,000
+Gap
+
+3
+00:00:02,000 --> 00:00:03,000
+Duplicate
+
+2
+00:00:03,000 --> 00:00:04,000
+Out of order
+
+99999999999
+00:00:04,000 --> 00:00:05,000
+Too large to keep
+";
+
+    let parsed = kiss_srt::ParseOptions::new().keep_ids(true).parse(TEXT);
+    assert!(parsed.errors.is_empty());
+    let ids: Vec<_> = parsed.subtitles.iter().map(|sub| sub.id).collect();
+    assert_eq!(ids, [Some(1), Some(3), Some(3), Some(2), None]);
+    let warnings: Vec<_> = parsed
+        .warnings
+        .iter()
+        .map(|warning| (warning.line, warning.kind))
+        .collect();
+    assert_eq!(
+        warnings,
+        [
+            (5, WarningKind::IdGap),
+            (9, WarningKind::DuplicateId),
+            (13, WarningKind::IdOutOfOrder),
+        ]
+    );
+
+    // IDs are thrown away by default
+    let subtitles = kiss_srt::from_str(TEXT).unwrap();
+    assert!(subtitles.iter().all(|sub| sub.id.is_none()));
+}
@@ -0,0 +1,133 @@
+use kiss_srt::{
+    document::{Cue, Document},
+    Duration, Subtitle, Timestamp,
+};
+
+// All kinds of trivia: a BOM, leading blank lines, mixed line endings, padded IDs, extra blank
+// lines between cues, stray carriage returns, and no trailing newline
+const MESSY: &str = "\u{feff}\n\n\
+001\r\n\
+00:00:00,000 --> 00:00:01,000\r\n\
+First\r\n\
+\r\n\
+\r\n\
+\r\n\
+002\n\
+00:00:01,000 --> 00:00:02,000 X1:1 X2:2  Y1:3 Y2:4\n\
+Second\r\r\n\
+with two lines\n\
+\r\n\
+3\n\
+00:00:02,000 --> 00:00:03,000\n\
+Third";
+
+#[test]
+fn untouched_is_byte_exact() {
+    let doc = Document::parse(MESSY).unwrap();
+    assert!(doc.has_bom());
+    assert_eq!(doc.to_string(), MESSY);
+    assert!(doc.cues().iter().all(|cue| !cue.is_modified()));
+}
+
+#[test]
+fn matches_from_str() {
+    let doc = Document::parse(MESSY).unwrap();
+    let mut subtitles = doc.to_subtitles();
+    for subtitle in &mut subtitles {
+        subtitle.id = None;
+    }
+    assert_eq!(
+        subtitles,
+        kiss_srt::from_str(MESSY.trim_start_matches('\u{feff}')).unwrap()
+    );
+}
+
+#[test]
+fn edits_only_touch_their_part() {
+    let mut doc = Document::parse(MESSY).unwrap();
+    {
+        let cues = doc.cues_mut();
+        cues[0].subtitle.start += Duration::from_millis(500);
+        cues[1].subtitle.text = String::from("Replaced");
+        cues[2].subtitle.id = Some(30);
+    }
+    assert!(doc.cues().iter().all(Cue::is_modified));
+
+    let expected = MESSY
+        .replace(
+            "00:00:00,000 --> 00:00:01,000\r\n",
+            "00:00:00,500 --> 00:00:01,500\r\n",
+        )
+        .replace("Second\r\r\nwith two lines\n", "Replaced\r\n")
+        .replace("\n3\n", "\n30\r\n");
+    assert_eq!(doc.to_string(), expected);
+}
+
+#[test]
+fn adding_and_removing_cues() {
+    let new_cue = Cue::new(Subtitle {
+        start: Timestamp::from_millis(5_000),
+        duration: Duration::from_millis(1_000),
+        text: String::from("New"),
+        position: None,
+        id: None,
+    });
+
+    let mut doc = Document::parse(MESSY).unwrap();
+    doc.cues_mut().remove(1);
+    doc.cues_mut().push(new_cue);
+    let rendered = doc.to_string();
+    assert!(rendered.starts_with("\u{feff}\n\n001\r\n"));
+    assert!(rendered.ends_with("Third\r\n\r\n3\r\n00:00:05,000 --> 00:00:06,000\r\nNew\r\n"));
+    assert_eq!(kiss_srt::from_str(&rendered[3..]).unwrap().len(), 3);
+}
+
+#[test]
+fn inserting_cues() {
+    const TEXT: &str = "\
+1\r\n\
+00:00:01,000 --> 00:00:02,000\r\n\
+First\r\n\
+\r\n\
+2\r\n\
+00:00:03,000 --> 00:00:04,000\r\n\
+Second\r\n";
+    let new_cue = |text: &str| {
+        Cue::new(Subtitle {
+            start: Timestamp::from_millis(0),
+            duration: Duration::from_millis(500),
+            text: String::from(text),
+            position: None,
+            id: None,
+        })
+    };
+
+    let mut doc = Document::parse(TEXT).unwrap();
+    doc.cues_mut().insert(0, new_cue("Before"));
+    doc.cues_mut().insert(2, new_cue("Between"));
+    let rendered = doc.to_string();
+    assert!(rendered.starts_with("1\r\n00:00:00,000 --> 00:00:00,500\r\nBefore\r\n\r\n1\r\n"));
+
+    let texts: Vec<_> = kiss_srt::from_str(&rendered)
+        .unwrap()
+        .into_iter()
+        .map(|subtitle| subtitle.text)
+        .collect();
+    assert_eq!(texts, ["Before", "First", "Between", "Second"]);
+}
+
+#[test]
+fn from_subtitles() {
+    let subtitles = kiss_srt::from_str(MESSY.trim_start_matches('\u{feff}')).unwrap();
+    let doc = Document::from(subtitles.clone());
+    assert_eq!(doc.to_string(), kiss_srt::to_string(&subtitles));
+}
+
+#[test]
+fn errors_match_from_str() {
+    let text = "1\n00:00:00.000 --> 00:00:01,000\nBad\n";
+    assert_eq!(
+        Document::parse(text).unwrap_err(),
+        kiss_srt::from_str(text).unwrap_err()
+    );
+}
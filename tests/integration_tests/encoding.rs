@@ -0,0 +1,147 @@
+use kiss_srt::{error::ErrorKind, Encoding};
+
+const TEXT: &str = "1\n00:00:00,000 --> 00:00:01,000\n“Café” — naïve\n";
+
+fn utf16(text: &str, to_bytes: fn(u16) -> [u8; 2]) -> Vec<u8> {
+    text.encode_utf16()
+        .flat_map(|unit| to_bytes(unit).to_vec())
+        .collect()
+}
+
+#[test]
+fn detects_and_decodes() {
+    let mut with_bom = b"\xef\xbb\xbf".to_vec();
+    with_bom.extend_from_slice(TEXT.as_bytes());
+    let windows_1252 = b"1\n00:00:00,000 --> 00:00:01,000\n\x93Caf\xe9\x94 \x97 na\xefve\n";
+
+    let cases = [
+        (TEXT.as_bytes().to_vec(), Encoding::Utf8),
+        (with_bom, Encoding::Utf8),
+        (utf16(TEXT, u16::to_le_bytes), Encoding::Utf16Le),
+        (utf16(TEXT, u16::to_be_bytes), Encoding::Utf16Be),
+        (
+            utf16(&format!("\u{feff}{}", TEXT), u16::to_le_bytes),
+            Encoding::Utf16Le,
+        ),
+        (
+            utf16(&format!("\u{feff}{}", TEXT), u16::to_be_bytes),
+            Encoding::Utf16Be,
+        ),
+        (windows_1252.to_vec(), Encoding::Windows1252),
+    ];
+
+    let expected = kiss_srt::from_str(TEXT).unwrap();
+    for (bytes, encoding) in cases.iter() {
+        let (subtitles, detected) = kiss_srt::from_bytes(bytes).unwrap();
+        assert_eq!(detected, *encoding);
+        assert_eq!(subtitles, expected, "{}", encoding);
+    }
+}
+
+#[test]
+fn utf16_with_nul_bytes_in_the_text() {
+    // Both `一` (U+4E00) and the ideographic space (U+3000) have a NUL low byte
+    let text = "1\n00:00:00,000 --> 00:00:01,000\n一\u{3000}二\n";
+    let expected = kiss_srt::from_str(text).unwrap();
+
+    for &(to_bytes, encoding) in &[
+        (u16::to_le_bytes as fn(u16) -> [u8; 2], Encoding::Utf16Le),
+        (u16::to_be_bytes, Encoding::Utf16Be),
+    ] {
+        let (subtitles, detected) = kiss_srt::from_bytes(&utf16(text, to_bytes)).unwrap();
+        assert_eq!(detected, encoding);
+        assert_eq!(subtitles, expected);
+    }
+}
+
+#[test]
+fn latin1_override() {
+    // 0x80 is a C1 control in Latin-1, but the Euro sign in Windows-1252
+    let bytes = b"\x80";
+    assert_eq!(Encoding::Latin1.decode(bytes).unwrap(), "\u{80}");
+    assert_eq!(Encoding::Windows1252.decode(bytes).unwrap(), "€");
+}
+
+#[test]
+fn single_byte_overrides_strip_a_utf8_bom() {
+    let bytes = b"\xef\xbb\xbf1";
+    assert_eq!(Encoding::Latin1.decode(bytes).unwrap(), "1");
+    assert_eq!(Encoding::Windows1252.decode(bytes).unwrap(), "1");
+    assert_eq!(Encoding::Iso8859_2.decode(bytes).unwrap(), "1");
+    assert_eq!(Encoding::Gb18030.decode(bytes).unwrap(), "1");
+}
+
+#[test]
+fn iso_8859() {
+    let cases = [
+        (Encoding::Iso8859_2, &b"\xa3\xf3d\xbc"[..], "Łódź"),
+        (Encoding::Iso8859_5, b"\xbf\xe0\xd8\xd2\xd5\xe2", "Привет"),
+        (Encoding::Iso8859_7, b"\xe3\xe5\xe9\xdc", "γειά"),
+        (Encoding::Iso8859_9, b"\xddstanbul \xfe", "İstanbul ş"),
+        (Encoding::Iso8859_15, b"\xa4 \xbd", "€ œ"),
+        // Unassigned bytes get replaced
+        (Encoding::Iso8859_3, b"\xa5", "\u{fffd}"),
+    ];
+
+    for &(encoding, bytes, expected) in cases.iter() {
+        assert_eq!(encoding.decode(bytes).unwrap(), expected, "{}", encoding);
+    }
+}
+
+#[test]
+fn gb18030() {
+    let text = "1\n00:00:00,000 --> 00:00:01,000\n你好，世界 € ɑ 😀\n";
+    let bytes = b"1\n00:00:00,000 --> 00:00:01,000\n\
+        \xc4\xe3\xba\xc3\xa3\xac\xca\xc0\xbd\xe7 \x80 \xa8\xbb \x949\xfc6\n";
+    let expected = kiss_srt::from_str(text).unwrap();
+
+    let (subtitles, detected) = kiss_srt::from_bytes(bytes).unwrap();
+    assert_eq!(detected, Encoding::Gb18030);
+    assert_eq!(subtitles, expected);
+
+    // A four-byte BOM
+    let mut with_bom = b"\x841\x953".to_vec();
+    with_bom.extend_from_slice(bytes);
+    assert_eq!(Encoding::detect(&with_bom), Encoding::Gb18030);
+    assert_eq!(kiss_srt::from_bytes(&with_bom).unwrap().0, expected);
+
+    // Truncated sequences
+    let err = Encoding::Gb18030.decode(b"1\n\xc4").unwrap_err();
+    assert_eq!((err.line, err.span.start), (2, 0));
+    let err = Encoding::Gb18030.decode(b"\x949\xfc").unwrap_err();
+    assert_eq!((err.line, err.kind), (1, ErrorKind::InvalidEncoding));
+}
+
+#[test]
+fn from_bytes_with() {
+    let bytes = b"1\n00:00:00,000 --> 00:00:01,000\n\xa3\xf3d\xbc\n";
+
+    // Detected as Windows-1252 which isn't what we want at all
+    let (subtitles, detected) = kiss_srt::from_bytes(bytes).unwrap();
+    assert_eq!(detected, Encoding::Windows1252);
+    assert_eq!(subtitles[0].text, "£ód¼");
+
+    let subtitles = kiss_srt::from_bytes_with(bytes, Encoding::Iso8859_2).unwrap();
+    assert_eq!(subtitles[0].text, "Łódź");
+    let err = kiss_srt::from_bytes_with(bytes, Encoding::Utf8).unwrap_err();
+    assert_eq!((err.line, err.kind), (3, ErrorKind::InvalidEncoding));
+}
+
+#[test]
+fn decoding_errors() {
+    let err = Encoding::Utf8.decode(b"1\n00:\xff").unwrap_err();
+    assert_eq!(
+        (err.line, err.span.start, err.kind),
+        (2, 3, ErrorKind::InvalidEncoding)
+    );
+
+    // Unpaired surrogate
+    let mut bytes = utf16("1\n", u16::to_le_bytes);
+    bytes.extend_from_slice(&0xd800_u16.to_le_bytes());
+    let err = Encoding::Utf16Le.decode(&bytes).unwrap_err();
+    assert_eq!((err.line, err.kind), (2, ErrorKind::InvalidEncoding));
+
+    // Dangling byte
+    let err = Encoding::Utf16Be.decode(b"\x00a\x00").unwrap_err();
+    assert_eq!((err.line, err.span.start), (1, 1));
+}
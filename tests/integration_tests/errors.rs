@@ -1,10 +1,13 @@
+use kiss_srt::error::TimestampPart;
+
 macro_rules! snapshot_parse_err {
     ( $( ($fn_name:ident, $text:expr ) ),* $(,)? ) => {
         $(
         #[test]
         fn $fn_name() {
             let err = kiss_srt::from_str($text).unwrap_err();
-            ::insta::assert_display_snapshot!(err);
+            ::insta::assert_snapshot!(err.to_string());
+            ::insta::assert_snapshot!(err.annotate($text).to_string());
         }
         )*
     };
@@ -60,3 +63,28 @@ snapshot_parse_err!(
     (missing_ts_line, MISSING_TS_LINE),
     (invalid_ts_divider, INVALID_TS_DIVIDER),
 );
+
+#[test]
+fn spans() {
+    let cases = [
+        (BAD_START_TS, 8..9, Some(TimestampPart::SecondsSeparator)),
+        (INVALID_TS_DIGIT, 23..25, Some(TimestampPart::Seconds)),
+        (BAD_ID, 0..6, None),
+        (TS_END_BEFORE_START, 17..29, None),
+        (TS_OUT_OF_BOUNDS, 3..5, Some(TimestampPart::Minutes)),
+        (MISSING_TS_LINE, 0..0, None),
+        (INVALID_TS_DIVIDER, 12..17, None),
+    ];
+
+    for (text, range, part) in cases.iter().cloned() {
+        let err = kiss_srt::from_str(text).unwrap_err();
+        assert_eq!(err.span.range(), range, "{:?}", text);
+        assert_eq!(err.part, part, "{:?}", text);
+    }
+}
+
+#[test]
+fn span_ends_on_a_char_boundary() {
+    let err = kiss_srt::from_str("1\n00:00:0é,000 --> 00:00:01,000\n").unwrap_err();
+    assert_eq!(err.span.range(), 6..9);
+}
@@ -0,0 +1,201 @@
+use kiss_srt::{
+    error::{ErrorKind, TimestampPart},
+    json, Duration, Position, Subtitle, Timestamp,
+};
+
+macro_rules! snapshot_import_err {
+    ( $( ($fn_name:ident, $text:expr ) ),* $(,)? ) => {
+        $(
+        #[test]
+        fn $fn_name() {
+            let err = json::from_str($text).unwrap_err();
+            ::insta::assert_snapshot!(err.to_string());
+            ::insta::assert_snapshot!(err.annotate($text).to_string());
+        }
+        )*
+    };
+}
+
+fn subtitles() -> Vec<Subtitle> {
+    vec![
+        Subtitle {
+            start: Timestamp::from_millis(500),
+            duration: Duration::from_millis(1_000),
+            text: String::from("Escapes \"quotes\", \\backslashes\\,\tand \u{1} controls"),
+            position: Some(Position {
+                x1: 100,
+                x2: 600,
+                y1: 50,
+                y2: 80,
+            }),
+            id: None,
+        },
+        Subtitle {
+            start: Timestamp::from_millis(2_000),
+            duration: Duration::from_millis(1_250),
+            text: String::from("Multiple\n<i>lines</i> with ünïcödé 🎉"),
+            position: None,
+            id: Some(10),
+        },
+    ]
+}
+
+const JSON: &str = r#"
+{"ignored": true}
+"#;
+
+const SUBTITLES: &str = "\u{feff}\
+[
+    {
+        \"text\": \"Keys in any order\\n\\nwith \\\"escapes\\\" \\u00e9 \\ud83c\\udf89\",
+        \"end\": 2500,
+        \"start\": 1000,
+        \"extra\": {\"nested\": [1, -2.5e3, null, false]}
+    },
+    {\"id\": null, \"start\": 3000, \"end\": 3000, \"text\": \"\", \"position\": null},
+    {\"id\": 7, \"start\": 4000, \"end\": 5000, \"text\": \"Explicit ID\"}
+]
+";
+
+#[test]
+fn import() {
+    let subtitles = json::from_str(SUBTITLES).unwrap();
+    assert_eq!(subtitles[2].id, None);
+    // Blank lines within the text get dropped
+    assert_eq!(
+        subtitles[0].text,
+        "Keys in any order\nwith \"escapes\" é 🎉"
+    );
+    insta::assert_snapshot!(kiss_srt::to_string(&subtitles));
+
+    let kept = json::ParseOptions::new()
+        .keep_ids(true)
+        .parse(SUBTITLES)
+        .unwrap();
+    assert_eq!(kept[2].id, Some(7));
+}
+
+#[test]
+fn export() {
+    let rendered = json::to_string(&subtitles());
+    insta::assert_snapshot!(rendered);
+
+    let mut io_rendered = Vec::new();
+    json::to_writer(&mut io_rendered, &subtitles()).unwrap();
+    assert_eq!(String::from_utf8(io_rendered).unwrap(), rendered);
+
+    assert_eq!(json::to_string(&[]), "[]\n");
+}
+
+#[test]
+fn round_trip() {
+    let rendered = json::to_string(&subtitles());
+    // The explicit ID is only kept when asked for
+    let mut expected = subtitles();
+    expected[1].id = None;
+    assert_eq!(json::from_str(&rendered).unwrap(), expected);
+    let options = json::ParseOptions::new().keep_ids(true);
+    assert_eq!(options.parse(&rendered).unwrap()[1].id, Some(10));
+    assert_eq!(json::from_str("[]").unwrap(), []);
+}
+
+#[test]
+fn deep_nesting() {
+    let nested = format!(
+        "[{{\"start\": 0, \"end\": 0, \"text\": \"\", \"extra\": {}{}}}]",
+        "[".repeat(100_000),
+        "]".repeat(100_000)
+    );
+    assert_eq!(
+        json::from_str(&nested).unwrap_err().kind,
+        ErrorKind::InvalidJson
+    );
+}
+
+#[test]
+fn crlf_text() {
+    let subtitles =
+        json::from_str(r#"[{"start": 0, "end": 0, "text": "Windows\r\n\r\nline endings\r\n"}]"#)
+            .unwrap();
+    assert_eq!(subtitles[0].text, "Windows\nline endings");
+}
+
+#[test]
+fn import_error_kinds() {
+    let err = |text| {
+        let err = json::from_str(text).unwrap_err();
+        (err.kind, err.part)
+    };
+    let invalid = (ErrorKind::InvalidJson, None);
+    let invalid_subtitle = (ErrorKind::InvalidJsonSubtitle, None);
+    let missing_key = (ErrorKind::MissingJsonKey, None);
+    let invalid_ts = (ErrorKind::InvalidJsonTimestamp, Some(TimestampPart::Millis));
+
+    assert_eq!(err(""), invalid);
+    assert_eq!(err(JSON), invalid);
+    assert_eq!(err("[] []"), invalid);
+    assert_eq!(err("[1,]"), invalid);
+    assert_eq!(err("[01]"), invalid);
+    assert_eq!(err("[\"\\x\"]"), invalid);
+    assert_eq!(err("[\"\\ud83c\"]"), invalid);
+    assert_eq!(err("[\"raw\nnewline\"]"), invalid);
+    assert_eq!(err("[1]"), invalid_subtitle);
+    assert_eq!(err("[{\"start\": 0, \"end\": 0}]"), missing_key);
+    assert_eq!(
+        err("[{\"start\": 0, \"end\": 0, \"text\": 1}]"),
+        invalid_subtitle
+    );
+    assert_eq!(
+        err("[{\"start\": 0, \"end\": 0, \"text\": \"\", \"id\": -1}]"),
+        invalid_subtitle
+    );
+    assert_eq!(
+        err("[{\"start\": 0, \"end\": 0, \"text\": \"\", \"position\": {\"x1\": 1}}]"),
+        invalid_subtitle
+    );
+    assert_eq!(
+        err("[{\"start\": 1.5, \"end\": 2, \"text\": \"\"}]"),
+        invalid_ts
+    );
+    assert_eq!(
+        err("[{\"start\": 0, \"end\": \"1000\", \"text\": \"\"}]"),
+        invalid_ts
+    );
+    assert_eq!(
+        err("[{\"start\": 0, \"end\": 999999999999, \"text\": \"\"}]"),
+        invalid_ts
+    );
+    assert_eq!(
+        err("[{\"start\": 2, \"end\": 1, \"text\": \"\"}]"),
+        (ErrorKind::TimestampEndBeforeStart, None)
+    );
+}
+
+const MISSING_COMMA: &str = r#"[
+  {"start": 1000, "end": 2000, "text": "First"}
+  {"start": 3000, "end": 4000, "text": "Second"}
+]
+"#;
+
+const MISSING_TEXT: &str = r#"[
+  {"start": 1000, "end": 2000, "text": "First"},
+  {"start": 3000, "end": 4000, "txet": "Typo"}
+]
+"#;
+
+const STRING_TIMESTAMP: &str = r#"[
+  {"start": "00:00:01,000", "end": 2000, "text": "First"}
+]
+"#;
+
+const UNCLOSED_STRING: &str = r#"[
+  {"start": 1000, "end": 2000, "text": "Never closed}
+]
+"#;
+
+snapshot_import_err!(
+    (missing_comma, MISSING_COMMA),
+    (missing_text, MISSING_TEXT),
+    (string_timestamp, STRING_TIMESTAMP),
+    (unclosed_string, UNCLOSED_STRING),
+);
@@ -0,0 +1,179 @@
+use kiss_srt::{
+    error::ErrorKind,
+    microdvd::{self, FrameRate},
+    Duration, Subtitle, Timestamp,
+};
+
+macro_rules! snapshot_import_err {
+    ( $( ($fn_name:ident, $text:expr ) ),* $(,)? ) => {
+        $(
+        #[test]
+        fn $fn_name() {
+            let err = microdvd::from_str($text, FrameRate::PAL).unwrap_err();
+            ::insta::assert_snapshot!(err.to_string());
+            ::insta::assert_snapshot!(err.annotate($text).to_string());
+        }
+        )*
+    };
+}
+
+#[test]
+fn frame_rates() {
+    let fps = |fps| FrameRate::from_fps(fps).unwrap();
+    assert_eq!(fps(23.976), FrameRate::FILM_NTSC);
+    assert_eq!(fps(23.98), FrameRate::FILM_NTSC);
+    assert_eq!(fps(29.97), FrameRate::NTSC);
+    assert_eq!(fps(24.0), FrameRate::FILM);
+    assert_eq!(fps(25.0), FrameRate::PAL);
+    assert_eq!(fps(59.94), FrameRate::new(60_000, 1_001).unwrap());
+    assert_eq!(fps(12.5), FrameRate::new(25, 2).unwrap());
+    assert_eq!(FrameRate::new(50, 2), Some(FrameRate::PAL));
+    assert_eq!(FrameRate::new(0, 1), None);
+    assert_eq!(FrameRate::from_fps(0.0), None);
+    assert_eq!(FrameRate::from_fps(-24.0), None);
+
+    let displayed: Vec<_> = [
+        FrameRate::FILM_NTSC,
+        FrameRate::FILM,
+        FrameRate::PAL,
+        FrameRate::NTSC,
+        fps(12.5),
+    ]
+    .iter()
+    .map(ToString::to_string)
+    .collect();
+    assert_eq!(displayed, ["23.976", "24", "25", "29.97", "12.5"]);
+}
+
+#[test]
+fn saturates_huge_frames() {
+    let max = std::u32::MAX;
+    for &rate in &[
+        FrameRate::PAL,
+        FrameRate::new(1, max).unwrap(),
+        FrameRate::new(max, max - 1).unwrap(),
+    ] {
+        assert_eq!(rate.to_timestamp(max), Timestamp::MAX, "{}", rate);
+    }
+    assert_eq!(FrameRate::new(1, max).unwrap().to_frame(Timestamp::MAX), 0);
+    assert_eq!(
+        FrameRate::new(max, 1).unwrap().to_frame(Timestamp::MAX),
+        max
+    );
+}
+
+#[test]
+fn rounding() {
+    // 1 frame at 24fps is 41.666...ms
+    assert_eq!(FrameRate::FILM.to_timestamp(1), Timestamp::from_millis(42));
+    // 3 frames is exactly 125ms
+    assert_eq!(FrameRate::FILM.to_timestamp(3), Timestamp::from_millis(125));
+    // Ties round up to the next frame
+    assert_eq!(FrameRate::FILM.to_frame(Timestamp::from_millis(62)), 1);
+    // 125ms is exactly half of a frame at 4fps
+    assert_eq!(
+        FrameRate::new(4, 1)
+            .unwrap()
+            .to_frame(Timestamp::from_millis(125)),
+        1
+    );
+    // Saturates instead of overflowing
+    assert_eq!(
+        FrameRate::new(1, 1).unwrap().to_timestamp(std::u32::MAX),
+        Timestamp::MAX
+    );
+
+    // Converting frames to timestamps and back is lossless
+    for &fps in &[FrameRate::FILM_NTSC, FrameRate::PAL, FrameRate::NTSC] {
+        for frame in (0..1_000_000).step_by(997) {
+            assert_eq!(fps.to_frame(fps.to_timestamp(frame)), frame);
+        }
+    }
+}
+
+const SUB: &str = "\
+{1}{1}23.976
+{0}{24}Plain text|on two lines
+
+{24}{48}{y:i}Italic line|Normal line
+{48}{72}{Y:b,u}All bold|and underlined
+{72}{96}/Slash italics|{c:$0000ff}{f:Arial}Colored { brace
+{96}{120}{y:i}{y:b}|
+";
+
+#[test]
+fn import() {
+    // The header's frame rate is used instead
+    let subtitles = microdvd::from_str(SUB, FrameRate::PAL).unwrap();
+    insta::assert_snapshot!(kiss_srt::to_string(&subtitles));
+}
+
+#[test]
+fn no_header() {
+    let subtitles = microdvd::from_str("{25}{50}Text\n", FrameRate::PAL).unwrap();
+    assert_eq!(subtitles[0].start, Timestamp::from_millis(1_000));
+    assert_eq!(subtitles[0].duration, Duration::from_millis(1_000));
+}
+
+#[test]
+fn export() {
+    let subtitles = vec![
+        Subtitle {
+            start: Timestamp::from_millis(0),
+            duration: Duration::from_millis(1_000),
+            text: String::from("<i>Spans\nmultiple</i>\nlines"),
+            position: None,
+            id: None,
+        },
+        Subtitle {
+            start: Timestamp::from_millis(1_000),
+            duration: Duration::from_millis(1_000),
+            text: String::from(
+                "Partially <b>bold</b> and <u><font color=\"red\">red</font></u> <3",
+            ),
+            position: None,
+            id: None,
+        },
+    ];
+
+    let sub = microdvd::to_string(&subtitles, FrameRate::NTSC);
+    insta::assert_snapshot!(sub);
+
+    let mut io_rendered = Vec::new();
+    microdvd::to_writer(&mut io_rendered, &subtitles, FrameRate::NTSC).unwrap();
+    assert_eq!(String::from_utf8(io_rendered).unwrap(), sub);
+}
+
+#[test]
+fn round_trip() {
+    let subtitles = microdvd::from_str(SUB, FrameRate::PAL).unwrap();
+    let sub = microdvd::to_string(&subtitles, FrameRate::FILM_NTSC);
+    assert_eq!(microdvd::from_str(&sub, FrameRate::PAL).unwrap(), subtitles);
+}
+
+#[test]
+fn import_error_kinds() {
+    let kind = |text| microdvd::from_str(text, FrameRate::PAL).unwrap_err().kind;
+    assert_eq!(
+        kind("{10}{5}Backwards\n"),
+        ErrorKind::TimestampEndBeforeStart
+    );
+    assert_eq!(kind("{10}Missing end\n"), ErrorKind::InvalidFrameNumber);
+    assert_eq!(
+        kind("{99999999999}{99999999999}Too big\n"),
+        ErrorKind::InvalidFrameNumber
+    );
+}
+
+const SRT: &str = "\
+1
+00:00:00,000 --> 00:00:01,000
+This is SRT
+";
+
+const UNCLOSED: &str = "\
+{1}{1}25
+{25}{50Unclosed end frame
+";
+
+snapshot_import_err!((not_microdvd, SRT), (unclosed_frame, UNCLOSED));
@@ -1,4 +1,22 @@
+mod ass;
+mod borrowed;
+mod csv;
+mod diagnostics;
+mod document;
+mod encoding;
 mod errors;
 mod fuzzer_crashes;
+mod json;
+mod microdvd;
 mod parsing;
+mod reader;
+mod recovery;
+mod rendering;
+mod sami;
+mod sbv;
+mod subviewer;
+mod text;
 mod time;
+mod transcript;
+mod ttml;
+mod webvtt;
@@ -74,3 +74,132 @@ fn empty_text_section() {
 
     "###);
 }
+
+#[test]
+fn tolerant_timestamps() {
+    let options = kiss_srt::ParseOptions::new().tolerant_timestamps(true);
+    let cases = [
+        ("00:00:01.500", 1_500),
+        ("0:00:01,5", 1_500),
+        ("00:00:01,50", 1_500),
+        ("00:00:01.05", 1_050),
+        ("1:2:3.4", ((60 + 2) * 60 + 3) * 1_000 + 400),
+    ];
+
+    for &(ts, millis) in &cases {
+        let text = format!("1\n{} --> 99:00:00,000\nText\n", ts);
+        // Strict parsing is still the default
+        assert!(kiss_srt::from_str(&text).is_err(), "{}", ts);
+        let subtitles = options.parse(&text).into_result().unwrap();
+        assert_eq!(subtitles[0].start.total_millis(), millis, "{}", ts);
+        assert_eq!(
+            kiss_srt::from_str(&kiss_srt::to_string(&subtitles)).unwrap(),
+            subtitles
+        );
+    }
+}
+
+#[test]
+fn tolerant_timestamps_still_validate() {
+    let options = kiss_srt::ParseOptions::new().tolerant_timestamps(true);
+    for ts in &[
+        "00:60:00,000",
+        "00:00:00;000",
+        "000:00:00,000",
+        "00:00:00,",
+        "::,",
+    ] {
+        let text = format!("1\n{} --> 99:00:00,000\nText\n", ts);
+        assert!(options.parse(&text).into_result().is_err(), "{}", ts);
+    }
+}
+
+const POSITIONS: &str = "\
+1
+00:00:00,000 --> 00:00:01,000 X1:100 X2:600 Y1:50 Y2:80
+Positioned
+
+2
+00:00:01,000 --> 00:00:02,000
+Not positioned
+";
+
+#[test]
+fn positions_round_trip() {
+    let subtitles = kiss_srt::from_str(POSITIONS).unwrap();
+    assert_eq!(
+        subtitles[0].position,
+        Some(kiss_srt::Position {
+            x1: 100,
+            x2: 600,
+            y1: 50,
+            y2: 80
+        })
+    );
+    assert_eq!(subtitles[1].position, None);
+    assert_eq!(kiss_srt::to_string(&subtitles), POSITIONS);
+}
+
+#[test]
+fn positions_from_constructors() {
+    use kiss_srt::{Position, Subtitle, SubtitleText, Timestamp};
+    use std::convert::TryFrom;
+
+    let ms = Timestamp::from_millis;
+    let text = |text| SubtitleText::try_from(text).unwrap();
+    let subtitles = [
+        Subtitle::new(ms(0), Duration::from_millis(1_000), text("Positioned")).with_position(
+            Position {
+                x1: 100,
+                x2: 600,
+                y1: 50,
+                y2: 80,
+            },
+        ),
+        Subtitle::new(
+            ms(1_000),
+            Duration::from_millis(1_000),
+            text("Not positioned"),
+        ),
+    ];
+    assert_eq!(kiss_srt::from_str(POSITIONS).unwrap(), subtitles);
+}
+
+#[test]
+fn malformed_positions() {
+    for trailing in &[
+        " X1:100 X2:600 Y1:50",
+        " X1:100 X2:600 Y1:50 Y2:",
+        " X1:100 X2:600 Y1:50 Y2:80 ",
+        " x1:100 x2:600 y1:50 y2:80",
+        "X1:100 X2:600 Y1:50 Y2:80",
+    ] {
+        let text = format!("1\n00:00:00,000 --> 00:00:01,000{}\nText\n", trailing);
+        let err = kiss_srt::from_str(&text).unwrap_err();
+        assert_eq!(err.kind, kiss_srt::error::ErrorKind::InvalidTimestampLine);
+    }
+}
+
+#[test]
+fn parse_into_reuses_and_truncates() {
+    const ONE: &str = "1\n00:00:00,000 --> 00:00:01,000\nOnly cue\n";
+
+    let mut subtitles = kiss_srt::from_str(SANITY).unwrap();
+    subtitles[0].id = Some(42);
+    kiss_srt::parse_into(ONE, &mut subtitles).unwrap();
+    assert_eq!(subtitles, kiss_srt::from_str(ONE).unwrap());
+
+    kiss_srt::parse_into(SANITY, &mut subtitles).unwrap();
+    assert_eq!(subtitles, kiss_srt::from_str(SANITY).unwrap());
+}
+
+#[test]
+fn parse_into_keeps_cues_before_an_error() {
+    const BAD: &str = "1\n00:00:00,000 --> 00:00:01,000\nFine\n\nnope\n";
+
+    let mut subtitles = kiss_srt::from_str(SANITY).unwrap();
+    let err = kiss_srt::parse_into(BAD, &mut subtitles).unwrap_err();
+    assert_eq!(err, kiss_srt::from_str(BAD).unwrap_err());
+    assert_eq!(subtitles.len(), 1);
+    assert_eq!(subtitles[0].text, "Fine");
+}
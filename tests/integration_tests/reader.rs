@@ -0,0 +1,95 @@
+use std::io::{self, BufReader, Read};
+
+use kiss_srt::{error::ReadError, ParseOptions, Reader, Recovery};
+
+const TEXT: &str = "\
+1\r
+00:00:00,000 --> 00:00:01,000\r
+CRLF line endings\r
+\r
+2
+00:00:01,000 --> 00:00:02,000
+Mixed with
+LF line endings
+
+
+3
+00:00:02,000 --> 00:00:03,000
+";
+
+#[test]
+fn matches_from_str() {
+    let read: Vec<_> = Reader::new(TEXT.as_bytes())
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(read, kiss_srt::from_str(TEXT).unwrap());
+}
+
+#[test]
+fn same_error_lines() {
+    const BAD: &str = "1\n00:00:00,000 --> 00:00:01,000\nFine\n\nnope\n";
+
+    let mut reader = Reader::new(BAD.as_bytes());
+    assert!(reader.next().unwrap().is_ok());
+    match reader.next().unwrap() {
+        Err(ReadError::Parse(err)) => assert_eq!(err, kiss_srt::from_str(BAD).unwrap_err()),
+        other => panic!("Expected a parse error. Found: {:?}", other),
+    }
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn keeps_going_when_recovering() {
+    const BAD: &str = "\
+1
+00:00:00.000 --> 00:00:01,000
+Skipped
+
+2
+00:00:01,000 --> 00:00:02,000
+Kept
+";
+
+    let options = ParseOptions::new().recovery(Recovery::Skip);
+    let items: Vec<_> = Reader::with_options(BAD.as_bytes(), options).collect();
+    assert_eq!(items.len(), 2);
+    assert!(items[0].is_err());
+    assert_eq!(items[1].as_ref().unwrap().text, "Kept");
+}
+
+struct FailAfter<'a> {
+    bytes: &'a [u8],
+}
+
+impl Read for FailAfter<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.bytes.is_empty() {
+            Err(io::Error::new(io::ErrorKind::Other, "Oh no!"))
+        } else {
+            self.bytes.read(buf)
+        }
+    }
+}
+
+#[test]
+fn io_errors() {
+    let reader = FailAfter {
+        bytes: b"1\n00:00:00,000 --> 00:00:01,000\nFine\n\n2\n",
+    };
+    let mut reader = Reader::new(BufReader::new(reader));
+    assert!(reader.next().unwrap().is_ok());
+    match reader.next().unwrap() {
+        Err(ReadError::Io(err)) => assert_eq!(err.to_string(), "Oh no!"),
+        other => panic!("Expected an IO error. Found: {:?}", other),
+    }
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn invalid_utf8() {
+    let mut reader = Reader::new(&b"1\n00:00:00,000 --> 00:00:01,000\n\xff\n"[..]);
+    match reader.next().unwrap() {
+        Err(ReadError::Io(err)) => assert_eq!(err.kind(), io::ErrorKind::InvalidData),
+        other => panic!("Expected an IO error. Found: {:?}", other),
+    }
+}
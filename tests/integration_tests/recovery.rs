@@ -0,0 +1,112 @@
+use kiss_srt::{error::ErrorKind, Duration, ParseOptions, Recovery};
+
+const MALFORMED: &str = "\
+1
+00:00:00,000 --> 00:00:01,000
+Fine
+
+bad id
+00:00:01,000 --> 00:00:02,000
+The ID is bad
+
+3
+00:00:03.000 --> 00:00:04,000
+The start uses a '.'
+
+00:00:04,000 --> 00:00:05,000
+Missing the ID
+
+5
+00:00:06,000 --> 00:00:05,000
+Ends before it starts
+
+6
+00:00:07,000 --> 00:00:08,000 trailing
+Trailing bytes
+
+7
+00:00:08,000 --> 00:00:09,000
+Also fine
+";
+
+fn error_kinds(errors: &[kiss_srt::error::Error]) -> Vec<(usize, ErrorKind)> {
+    errors.iter().map(|err| (err.line, err.kind)).collect()
+}
+
+#[test]
+fn abort_matches_from_str() {
+    let parsed = ParseOptions::new().parse(MALFORMED);
+    assert_eq!(parsed.subtitles.len(), 1);
+    assert_eq!(
+        parsed.into_result().unwrap_err(),
+        kiss_srt::from_str(MALFORMED).unwrap_err()
+    );
+}
+
+#[test]
+fn skip() {
+    let parsed = ParseOptions::new()
+        .recovery(Recovery::Skip)
+        .parse(MALFORMED);
+    let texts: Vec<_> = parsed
+        .subtitles
+        .iter()
+        .map(|sub| sub.text.as_str())
+        .collect();
+    assert_eq!(texts, ["Fine", "Also fine"]);
+    assert_eq!(
+        error_kinds(&parsed.errors),
+        [
+            (5, ErrorKind::InvalidId),
+            (10, ErrorKind::InvalidTimestampStart),
+            (13, ErrorKind::InvalidId),
+            (17, ErrorKind::TimestampEndBeforeStart),
+            (21, ErrorKind::InvalidTimestampLine),
+        ]
+    );
+}
+
+#[test]
+fn repair() {
+    let parsed = ParseOptions::new()
+        .recovery(Recovery::Repair)
+        .parse(MALFORMED);
+    let texts: Vec<_> = parsed
+        .subtitles
+        .iter()
+        .map(|sub| sub.text.as_str())
+        .collect();
+    assert_eq!(
+        texts,
+        [
+            "Fine",
+            "The ID is bad",
+            "Missing the ID",
+            "Ends before it starts",
+            "Trailing bytes",
+            "Also fine"
+        ]
+    );
+    assert_eq!(parsed.subtitles[3].duration, Duration::default());
+    assert_eq!(
+        error_kinds(&parsed.errors),
+        [
+            (5, ErrorKind::InvalidId),
+            (10, ErrorKind::InvalidTimestampStart),
+            (13, ErrorKind::InvalidId),
+            (17, ErrorKind::TimestampEndBeforeStart),
+            (21, ErrorKind::InvalidTimestampLine),
+        ]
+    );
+}
+
+#[test]
+fn empty_ts_line_still_ends_the_cue() {
+    const TEXT: &str = "1\n\n2\n00:00:00,000 --> 00:00:01,000\nKept\n";
+    let parsed = ParseOptions::new().recovery(Recovery::Skip).parse(TEXT);
+    assert_eq!(parsed.subtitles.len(), 1);
+    assert_eq!(
+        error_kinds(&parsed.errors),
+        [(2, ErrorKind::InvalidTimestampStart)]
+    );
+}
@@ -0,0 +1,235 @@
+use std::convert::TryFrom;
+
+use kiss_srt::{
+    error::RenderError, BlankLines, Duration, LineEnding, RenderOptions, Subtitle, SubtitleText,
+    Timestamp, Writer,
+};
+
+fn sample() -> Vec<Subtitle> {
+    (0..3)
+        .map(|i| Subtitle {
+            start: Timestamp::from_millis(i * 1_000),
+            duration: Duration::from_millis(500),
+            text: format!("Line {}\nSecond line", i),
+            position: None,
+            id: None,
+        })
+        .collect()
+}
+
+#[test]
+fn writers_match_to_string() {
+    let subtitles = sample();
+    let expected = kiss_srt::to_string(&subtitles);
+
+    let mut io_rendered = Vec::new();
+    kiss_srt::to_writer(&mut io_rendered, &subtitles).unwrap();
+    assert_eq!(String::from_utf8(io_rendered).unwrap(), expected);
+
+    let mut fmt_rendered = String::new();
+    kiss_srt::to_fmt_writer(&mut fmt_rendered, &subtitles).unwrap();
+    assert_eq!(fmt_rendered, expected);
+}
+
+#[test]
+fn nothing_to_render() {
+    let mut rendered = Vec::new();
+    kiss_srt::to_writer(&mut rendered, &[]).unwrap();
+    assert!(rendered.is_empty());
+    assert_eq!(kiss_srt::to_string(&[]), "");
+}
+
+#[test]
+fn incremental_writer() {
+    let subtitles = sample();
+
+    let mut writer = Writer::new(Vec::new());
+    for subtitle in &subtitles {
+        writer.write(subtitle).unwrap();
+    }
+    assert_eq!(writer.next_id(), 4);
+
+    let rendered = String::from_utf8(writer.into_inner()).unwrap();
+    assert_eq!(rendered, kiss_srt::to_string(&subtitles));
+    assert_eq!(kiss_srt::from_str(&rendered).unwrap(), subtitles);
+}
+
+#[test]
+fn explicit_ids() {
+    let mut subtitles = sample();
+    subtitles[1].id = Some(10);
+
+    let rendered = kiss_srt::to_string(&subtitles);
+    let ids: Vec<_> = rendered
+        .split("\n\n")
+        .map(|cue| cue.lines().next().unwrap())
+        .collect();
+    assert_eq!(ids, ["1", "10", "3"]);
+
+    let mut writer = Writer::new(Vec::new());
+    for subtitle in &subtitles {
+        writer.write(subtitle).unwrap();
+    }
+    assert_eq!(writer.into_inner(), rendered.into_bytes());
+}
+
+#[test]
+fn render_options() {
+    let subtitles = sample();
+    let options = RenderOptions::new()
+        .line_ending(LineEnding::CrLf)
+        .bom(true)
+        .trailing_blank_line(true)
+        .start_id(0);
+
+    let rendered = options.render(&subtitles);
+    insta::assert_debug_snapshot!(rendered);
+    // Still parses back to the same subtitles
+    let parsed = kiss_srt::from_bytes(rendered.as_bytes()).unwrap().0;
+    assert_eq!(parsed, subtitles);
+
+    let mut io_rendered = Vec::new();
+    options
+        .render_to_writer(&mut io_rendered, &subtitles)
+        .unwrap();
+    assert_eq!(io_rendered, rendered.into_bytes());
+}
+
+#[test]
+fn crlf_text() {
+    let subtitles = [Subtitle {
+        start: Timestamp::from_millis(0),
+        duration: Duration::from_millis(1_000),
+        text: String::from("Windows\r\nline endings"),
+        position: None,
+        id: None,
+    }];
+
+    assert_eq!(
+        RenderOptions::new()
+            .line_ending(LineEnding::CrLf)
+            .render(&subtitles),
+        "1\r\n00:00:00,000 --> 00:00:01,000\r\nWindows\r\nline endings\r\n",
+    );
+    assert_eq!(
+        kiss_srt::to_string(&subtitles),
+        "1\n00:00:00,000 --> 00:00:01,000\nWindows\nline endings\n",
+    );
+}
+
+#[test]
+fn start_id_saturates() {
+    let subtitles = sample();
+    let options = RenderOptions::new().start_id(std::u32::MAX - 1);
+
+    let rendered = options.render(&subtitles);
+    let ids: Vec<_> = rendered
+        .split("\n\n")
+        .map(|cue| cue.lines().next().unwrap())
+        .collect();
+    assert_eq!(ids, ["4294967294", "4294967295", "4294967295"]);
+
+    let mut writer = Writer::with_options(Vec::new(), options);
+    for subtitle in &subtitles {
+        writer.write(subtitle).unwrap();
+    }
+    assert_eq!(writer.next_id(), std::u32::MAX);
+    assert_eq!(writer.into_inner(), rendered.into_bytes());
+}
+
+#[test]
+fn explicit_ids_from_constructors() {
+    let subtitle = |id: Option<u32>| {
+        let subtitle = Subtitle::new(
+            Timestamp::from_millis(0),
+            Duration::from_millis(1_000),
+            SubtitleText::try_from("Text").unwrap(),
+        );
+        match id {
+            Some(id) => subtitle.with_id(id),
+            None => subtitle,
+        }
+    };
+    let subtitles = [subtitle(Some(10)), subtitle(None), subtitle(Some(7))];
+
+    let ids: Vec<_> = kiss_srt::to_string(&subtitles)
+        .split("\n\n")
+        .map(|cue| cue.lines().next().unwrap().to_owned())
+        .collect();
+    assert_eq!(ids, ["10", "2", "7"]);
+}
+
+#[test]
+fn default_render_options_match_to_string() {
+    let subtitles = sample();
+    assert_eq!(
+        RenderOptions::new().render(&subtitles),
+        kiss_srt::to_string(&subtitles)
+    );
+}
+
+#[test]
+fn try_to_string_rejects_empty_lines() {
+    let mut subtitles = sample();
+    assert_eq!(
+        kiss_srt::try_to_string(&subtitles).unwrap(),
+        kiss_srt::to_string(&subtitles)
+    );
+
+    // Empty text on its own is fine
+    subtitles[0].text.clear();
+    assert!(kiss_srt::try_to_string(&subtitles).is_ok());
+
+    subtitles[2].text = String::from("Trailing empty line\n");
+    let err = kiss_srt::try_to_string(&subtitles).unwrap_err();
+    assert_eq!(
+        err,
+        RenderError {
+            index: 2,
+            text_line: 2
+        }
+    );
+    insta::assert_snapshot!(err.to_string());
+
+    subtitles[1].text = String::from("\r\nLooks empty");
+    let err = kiss_srt::try_to_string(&subtitles).unwrap_err();
+    assert_eq!((err.index, err.text_line), (1, 1));
+}
+
+#[test]
+fn repaired_empty_lines_reparse() {
+    let mut subtitles = sample();
+    subtitles[0].text = String::from("\nLeading");
+    subtitles[1].text = String::from("Middle\n\n\nlines");
+    subtitles[2].text = String::from("\n");
+
+    let collapsed = RenderOptions::new()
+        .blank_lines(BlankLines::Collapse)
+        .try_render(&subtitles)
+        .unwrap();
+    let texts: Vec<_> = kiss_srt::from_str(&collapsed)
+        .unwrap()
+        .into_iter()
+        .map(|subtitle| subtitle.text)
+        .collect();
+    assert_eq!(texts, ["Leading", "Middle\nlines", ""]);
+
+    let spaced = RenderOptions::new()
+        .blank_lines(BlankLines::NonBreakingSpace)
+        .line_ending(LineEnding::CrLf)
+        .try_render(&subtitles)
+        .unwrap();
+    let texts: Vec<_> = kiss_srt::from_str(&spaced)
+        .unwrap()
+        .into_iter()
+        .map(|subtitle| subtitle.text)
+        .collect();
+    assert_eq!(
+        texts,
+        [
+            "\u{a0}\nLeading",
+            "Middle\n\u{a0}\n\u{a0}\nlines",
+            "\u{a0}\n\u{a0}"
+        ]
+    );
+}
@@ -0,0 +1,111 @@
+use kiss_srt::{error::ErrorKind, sami};
+
+macro_rules! snapshot_import_err {
+    ( $( ($fn_name:ident, $text:expr ) ),* $(,)? ) => {
+        $(
+        #[test]
+        fn $fn_name() {
+            let err = sami::from_str($text).unwrap_err();
+            ::insta::assert_snapshot!(err.to_string());
+            ::insta::assert_snapshot!(err.annotate($text).to_string());
+        }
+        )*
+    };
+}
+
+const SMI: &str = r##"<SAMI>
+<HEAD>
+<TITLE>Example</TITLE>
+<STYLE TYPE="text/css">
+<!--
+P { margin-left: 8pt; font-size: 20pt; text-align: center; }
+.KRCC { Name: Korean; lang: ko-KR; SAMIType: CC; }
+.ENCC { Name: English; lang: en-US; SAMIType: CC; }
+.FRCC { Name: French; lang: fr-FR; SAMIType: CC; }
+-->
+</STYLE>
+</HEAD>
+<BODY>
+<!-- <SYNC Start=0><P Class=ENCC>Commented out -->
+<SYNC Start=1000>
+<P Class=KRCC>첫 번째
+<P class="encc">First   line<BR>and the
+  <I>second</I> line</P>
+<Sync Start="1500"><P Class=KRCC>&nbsp;
+<SYNC Start=2000><P Class=ENCC><font color="#ffff00">Yellow</font> &amp; <span>plain</span>
+<SYNC Start=3000><P Class=DECC>Undeclared class
+<SYNC Start=2500><P Class=ENCC><br>&nbsp;<br/>
+<SYNC Start=4000>No paragraph
+<SYNC Start=5000><P Class=ENCC>Last of its track
+<SYNC Start=6000><P Class=KRCC>Last sync
+</BODY>
+</SAMI>
+"##;
+
+#[test]
+fn import() {
+    let tracks = sami::from_str(SMI).unwrap();
+    let summary: Vec<_> = tracks
+        .iter()
+        .map(|track| {
+            format!(
+                "[{}] {:?} {:?}\n{}",
+                track.class,
+                track.name,
+                track.lang,
+                kiss_srt::to_string(&track.subtitles)
+            )
+        })
+        .collect();
+    insta::assert_snapshot!(summary.join("\n"));
+}
+
+#[test]
+fn no_styles() {
+    let tracks = sami::from_str("<sami><body><sync start=0><p>Hi<sync start=10>").unwrap();
+    assert_eq!(tracks.len(), 1);
+    assert_eq!(tracks[0].class, "");
+    assert_eq!(tracks[0].name, None);
+    assert_eq!(tracks[0].subtitles[0].text, "Hi");
+    assert_eq!(tracks[0].subtitles[0].duration.total_millis(), 10);
+}
+
+#[test]
+fn escaped_tags_stay_escaped() {
+    let sami = "<SAMI><BODY><SYNC Start=0><P>&lt;u&gt;Not underlined<SYNC Start=1000><P>&nbsp;";
+    let tracks = sami::from_str(sami).unwrap();
+    let subtitles = &tracks[0].subtitles;
+    assert_eq!(subtitles[0].text, "&lt;u>Not underlined");
+    assert_eq!(
+        kiss_srt::to_string(subtitles),
+        "1\n00:00:00,000 --> 00:00:01,000\n&lt;u>Not underlined\n",
+    );
+}
+
+#[test]
+fn import_error_kinds() {
+    let kind = |text| sami::from_str(text).unwrap_err().kind;
+    assert_eq!(kind(""), ErrorKind::MissingSamiTag);
+    assert_eq!(kind("<SAMI><SYNC><P>No start"), ErrorKind::InvalidSyncStart);
+    assert_eq!(kind("<SAMI><SYNC Start=1"), ErrorKind::InvalidSyncStart);
+    assert_eq!(
+        kind("<SAMI><SYNC Start=-1><P>Negative"),
+        ErrorKind::InvalidSyncStart
+    );
+}
+
+const SRT: &str = "\
+1
+00:00:00,000 --> 00:00:01,000
+This is SRT
+";
+
+const BAD_START: &str = "\
+<SAMI>
+<BODY>
+<SYNC Start=1.5s><P Class=ENCC>Not millis
+</BODY>
+</SAMI>
+";
+
+snapshot_import_err!((not_sami, SRT), (bad_start, BAD_START));
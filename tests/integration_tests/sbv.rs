@@ -0,0 +1,106 @@
+use kiss_srt::{error::ErrorKind, sbv, Duration, Subtitle, Timestamp};
+
+macro_rules! snapshot_import_err {
+    ( $( ($fn_name:ident, $text:expr ) ),* $(,)? ) => {
+        $(
+        #[test]
+        fn $fn_name() {
+            let err = sbv::from_str($text).unwrap_err();
+            ::insta::assert_snapshot!(err.to_string());
+            ::insta::assert_snapshot!(err.annotate($text).to_string());
+        }
+        )*
+    };
+}
+
+const SBV: &str = "\u{feff}\
+0:00:00.000,0:00:01.500
+First caption
+
+  
+12:34:56.789,12:34:57.000  
+Extra blank lines
+and <i>tags</i> are left alone
+";
+
+#[test]
+fn import() {
+    let subtitles = sbv::from_str(SBV).unwrap();
+    insta::assert_snapshot!(kiss_srt::to_string(&subtitles));
+}
+
+#[test]
+fn export() {
+    let subtitles = vec![
+        Subtitle {
+            start: Timestamp::from_millis(500),
+            duration: Duration::from_millis(1_000),
+            text: String::from("<i>Tags</i> get <font color=\"red\">stripped</font> <3"),
+            position: None,
+            id: None,
+        },
+        Subtitle {
+            start: Timestamp::new(10, 0, 0, 0).unwrap(),
+            duration: Duration::from_millis(1),
+            text: String::from("Double digit\nhours"),
+            position: None,
+            id: None,
+        },
+    ];
+
+    let rendered = sbv::to_string(&subtitles);
+    insta::assert_snapshot!(rendered);
+
+    let mut io_rendered = Vec::new();
+    sbv::to_writer(&mut io_rendered, &subtitles).unwrap();
+    assert_eq!(String::from_utf8(io_rendered).unwrap(), rendered);
+}
+
+#[test]
+fn round_trip() {
+    let subtitles = kiss_srt::from_str(
+        "1\n00:00:01,000 --> 00:00:02,000\nSome text\n\n2\n00:00:03,000 --> 00:00:04,000\n",
+    )
+    .unwrap();
+    let rendered = sbv::to_string(&subtitles);
+    assert_eq!(sbv::from_str(&rendered).unwrap(), subtitles);
+}
+
+#[test]
+fn import_error_kinds() {
+    let kind = |text| sbv::from_str(text).unwrap_err().kind;
+    assert_eq!(
+        kind("0:00:02.000,0:00:01.000\n"),
+        ErrorKind::TimestampEndBeforeStart
+    );
+    assert_eq!(kind("0:00:01.000,\n"), ErrorKind::InvalidSbvTimestamp);
+    assert_eq!(
+        kind("0:00:01.000,0:00:02.000 trailing\n"),
+        ErrorKind::InvalidSbvTimestamp
+    );
+    assert_eq!(
+        kind("100:00:01.000,100:00:02.000\n"),
+        ErrorKind::InvalidSbvTimestamp
+    );
+}
+
+const SRT_TIMESTAMPS: &str = "\
+0:00:01,000,0:00:02,000
+Uses commas
+";
+
+const LONG_MILLIS: &str = "\
+0:00:01.0000,0:00:02.000
+Too precise
+";
+
+const MISSING_COMMA: &str = "\
+0:00:01.000 --> 0:00:02.000
+Uses an SRT divider
+";
+
+snapshot_import_err!(
+    (srt_timestamps, SRT_TIMESTAMPS),
+    (long_millis, LONG_MILLIS),
+    (missing_comma, MISSING_COMMA),
+);
@@ -0,0 +1,104 @@
+use kiss_srt::{error::ErrorKind, subviewer, Duration, Subtitle, Timestamp};
+
+macro_rules! snapshot_import_err {
+    ( $( ($fn_name:ident, $text:expr ) ),* $(,)? ) => {
+        $(
+        #[test]
+        fn $fn_name() {
+            let err = subviewer::from_str($text).unwrap_err();
+            ::insta::assert_snapshot!(err.to_string());
+            ::insta::assert_snapshot!(err.annotate($text).to_string());
+        }
+        )*
+    };
+}
+
+const SUB: &str = "\
+[INFORMATION]
+[TITLE]Example
+[AUTHOR]
+[SOURCE]
+[PRG]
+[FILEPATH]
+[DELAY]0
+[CD TRACK]0
+[COMMENT]
+[END INFORMATION]
+[SUBTITLE]
+[COLF]&HFFFFFF,[STYLE]bd,[SIZE]18,[FONT]Arial
+00:00:01.50,00:00:03.00
+First line[br]Second line
+
+00:00:04.00,00:00:05.25
+[br]Leading and trailing breaks[br]
+[br]
+Text on its own line
+
+00:00:06.00,00:00:07.00
+[music]
+";
+
+#[test]
+fn import() {
+    let subtitles = subviewer::from_str(SUB).unwrap();
+    insta::assert_snapshot!(kiss_srt::to_string(&subtitles));
+}
+
+#[test]
+fn export() {
+    let subtitles = vec![
+        Subtitle {
+            start: Timestamp::from_millis(1_234),
+            duration: Duration::from_millis(1_001),
+            text: String::from("<b>Rounded</b> to\ncentiseconds"),
+            position: None,
+            id: None,
+        },
+        Subtitle {
+            start: Timestamp::MAX,
+            duration: Duration::from_millis(0),
+            text: String::new(),
+            position: None,
+            id: None,
+        },
+    ];
+
+    let rendered = subviewer::to_string(&subtitles);
+    insta::assert_snapshot!(rendered);
+
+    let mut io_rendered = Vec::new();
+    subviewer::to_writer(&mut io_rendered, &subtitles).unwrap();
+    assert_eq!(String::from_utf8(io_rendered).unwrap(), rendered);
+}
+
+#[test]
+fn round_trip() {
+    let subtitles = subviewer::from_str(SUB).unwrap();
+    let rendered = subviewer::to_string(&subtitles);
+    assert_eq!(subviewer::from_str(&rendered).unwrap(), subtitles);
+}
+
+#[test]
+fn import_error_kinds() {
+    let kind = |text| subviewer::from_str(text).unwrap_err().kind;
+    assert_eq!(
+        kind("00:00:02.00,00:00:01.00\n"),
+        ErrorKind::TimestampEndBeforeStart
+    );
+    assert_eq!(
+        kind("00:00:01.00;00:00:02.00\n"),
+        ErrorKind::MissingTimestampComma
+    );
+    assert_eq!(
+        kind("0:00:01.00,0:00:02.00\n"),
+        ErrorKind::InvalidSubViewerTimestamp
+    );
+}
+
+const MILLIS: &str = "\
+[SUBTITLE]
+00:00:01.000,00:00:02.000
+Uses millis
+";
+
+snapshot_import_err!((millis, MILLIS));
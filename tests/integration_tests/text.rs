@@ -0,0 +1,64 @@
+use std::convert::TryFrom;
+
+use kiss_srt::{error::TextError, Duration, Subtitle, SubtitleText, Timestamp};
+
+#[test]
+fn rejects_empty_lines() {
+    for &(text, text_line) in &[
+        ("\nLeading", 1),
+        ("Middle\n\nempty line", 2),
+        ("Trailing\n", 2),
+        ("Carriage\n\r\nreturn", 2),
+    ] {
+        assert_eq!(
+            SubtitleText::try_from(text),
+            Err(TextError { text_line }),
+            "{:?}",
+            text
+        );
+    }
+
+    assert_eq!(SubtitleText::try_from("").unwrap(), SubtitleText::new());
+}
+
+#[test]
+fn push_line() {
+    let mut text = SubtitleText::new();
+    assert_eq!(text.push_line(""), Err(TextError { text_line: 1 }));
+    text.push_line("One").unwrap();
+    text.push_line("Two\nThree").unwrap();
+    assert_eq!(
+        text.push_line("Four\n\nSix"),
+        Err(TextError { text_line: 5 })
+    );
+    assert_eq!(text.push_line(""), Err(TextError { text_line: 4 }));
+
+    assert_eq!(&*text, "One\nTwo\nThree");
+    assert_eq!(text.lines().count(), 3);
+    assert_eq!(text.num_lines(), 3);
+
+    let mut text = SubtitleText::try_from("Already\ntwo lines").unwrap();
+    assert_eq!(text.num_lines(), 2);
+    assert_eq!(text.push_line("Third\n"), Err(TextError { text_line: 4 }));
+    text.push_line("Third").unwrap();
+    assert_eq!(text.num_lines(), 3);
+}
+
+#[test]
+fn always_renders_valid_srt() {
+    let mut text = SubtitleText::new();
+    text.push_line("Validated").unwrap();
+    text.push_line("text").unwrap();
+    let mut subtitles = vec![Subtitle::new(
+        Timestamp::from_millis(0),
+        Duration::from_millis(1_000),
+        text,
+    )];
+    subtitles.push(subtitles[0].clone());
+    subtitles[1].set_text(SubtitleText::try_from("Replaced").unwrap());
+
+    let rendered = kiss_srt::try_to_string(&subtitles).unwrap();
+    assert_eq!(kiss_srt::from_str(&rendered).unwrap(), subtitles);
+    assert_eq!(subtitles[0].text, "Validated\ntext");
+    assert_eq!(subtitles[1].text, "Replaced");
+}
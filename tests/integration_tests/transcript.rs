@@ -0,0 +1,76 @@
+use kiss_srt::{
+    transcript::{self, RenderOptions},
+    Duration, Subtitle, Timestamp,
+};
+
+fn subtitle(start: u32, end: u32, text: &str) -> Subtitle {
+    Subtitle {
+        start: Timestamp::from_millis(start),
+        duration: Duration::from_millis(end - start),
+        text: String::from(text),
+        position: None,
+        id: None,
+    }
+}
+
+fn subtitles() -> Vec<Subtitle> {
+    vec![
+        subtitle(
+            0,
+            1_000,
+            "<i>Tags</i> get <font color=\"red\">stripped</font>",
+        ),
+        subtitle(
+            1_500,
+            3_000,
+            "-  Line breaks\n-  and  extra   spaces collapse",
+        ),
+        subtitle(2_000, 6_000, "Overlapping cues stay together"),
+        subtitle(7_000, 8_000, "<b></b>"),
+        subtitle(8_000, 9_000, "Empty cues are skipped"),
+        subtitle(61_000, 62_000, "A long pause"),
+        subtitle(62_000, 63_000, "starts a new paragraph"),
+        subtitle(3_723_000, 3_724_000, "Hours later"),
+    ]
+}
+
+#[test]
+fn default() {
+    let rendered = transcript::to_string(&subtitles());
+    insta::assert_snapshot!(rendered);
+
+    let mut io_rendered = Vec::new();
+    transcript::to_writer(&mut io_rendered, &subtitles()).unwrap();
+    assert_eq!(String::from_utf8(io_rendered).unwrap(), rendered);
+
+    assert_eq!(transcript::to_string(&[]), "");
+    assert_eq!(transcript::to_string(&[subtitle(0, 0, "  ")]), "");
+}
+
+#[test]
+fn timestamps() {
+    let options = RenderOptions::new().timestamp_interval(Some(Duration::from_millis(5_000)));
+    insta::assert_snapshot!(options.render(&subtitles()));
+}
+
+#[test]
+fn paragraph_gap() {
+    let subtitles = subtitles();
+    let every_cue = RenderOptions::new()
+        .paragraph_gap(Duration::default())
+        .render(&subtitles[..3]);
+    assert_eq!(
+        every_cue,
+        "Tags get stripped\n\n\
+         - Line breaks - and extra spaces collapse\n\n\
+         Overlapping cues stay together\n",
+    );
+
+    let one_paragraph = RenderOptions::new()
+        .paragraph_gap(Timestamp::MAX)
+        .render(&subtitles[4..7]);
+    assert_eq!(
+        one_paragraph,
+        "Empty cues are skipped A long pause starts a new paragraph\n"
+    );
+}
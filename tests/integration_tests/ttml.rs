@@ -0,0 +1,242 @@
+use kiss_srt::{
+    error::ErrorKind,
+    ttml::{self, RenderOptions},
+    Duration, Subtitle, Timestamp,
+};
+
+macro_rules! snapshot_import_err {
+    ( $( ($fn_name:ident, $text:expr ) ),* $(,)? ) => {
+        $(
+        #[test]
+        fn $fn_name() {
+            let err = ttml::from_str($text).unwrap_err();
+            ::insta::assert_snapshot!(err.to_string());
+            ::insta::assert_snapshot!(err.annotate($text).to_string());
+        }
+        )*
+    };
+}
+
+const DFXP: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<!DOCTYPE tt>
+<tt:tt xmlns:tt="http://www.w3.org/ns/ttml" xmlns:tts="http://www.w3.org/ns/ttml#styling"
+  xmlns:ttp="http://www.w3.org/ns/ttml#parameter" ttp:frameRate="30"
+  ttp:frameRateMultiplier="1000 1001" ttp:tickRate="10000000" xml:lang="en">
+  <tt:head>
+    <tt:styling>
+      <tt:style xml:id="italic" tts:fontStyle="italic"/>
+      <tt:style xml:id="loud" style="italic" tts:fontWeight="bold"/>
+    </tt:styling>
+  </tt:head>
+  <tt:body>
+    <!-- A <p> in a comment doesn't count -->
+    <tt:div begin="10s">
+      <tt:p begin="00:00:00:15" dur="1.5s">Offset by the div<tt:br/>and using frames</tt:p>
+    </tt:div>
+    <tt:div>
+      <tt:p begin="5000000t" end="15000000t">
+        Out   of
+        order
+      </tt:p>
+      <tt:p begin="2s" end="3s" style="loud">Loud <tt:span tts:fontWeight="normal">and
+        <tt:span tts:textDecoration="underline">calm</tt:span></tt:span></tt:p>
+      <tt:p begin="00:00:03.000" end="00:00:04.000"><tt:br/>
+        &lt;Escaped&gt; &#x266A; &#9835; &unknown; <![CDATA[<raw> & text]]><tt:br/> <tt:br/></tt:p>
+      <tt:p begin="0.05h" end="181s"><tt:metadata/><tt:set tts:color="red"/>Units</tt:p>
+    </tt:div>
+  </tt:body>
+</tt:tt>
+"#;
+
+#[test]
+fn import() {
+    let subtitles = ttml::from_str(DFXP).unwrap();
+    insta::assert_snapshot!(kiss_srt::to_string(&subtitles));
+}
+
+#[test]
+fn time_expressions() {
+    let end = |params: &str, time: &str| {
+        let ttml = format!(
+            "<tt {}><body><p begin=\"0s\" end=\"{}\"/></body></tt>",
+            params, time
+        );
+        let subtitles = ttml::from_str(&ttml).unwrap();
+        subtitles[0].duration.total_millis()
+    };
+
+    assert_eq!(end("", "01:02:03.456"), 3_723_456);
+    assert_eq!(end("", "99:00:00"), 356_400_000);
+    assert_eq!(end("", "00:00:01.5"), 1_500);
+    assert_eq!(end("", "00:00:01.0005"), 1_001);
+    // 30fps by default
+    assert_eq!(end("", "00:00:01:15"), 1_500);
+    assert_eq!(end("", "00:00:01:15.1"), 1_500);
+    assert_eq!(end("ttp:frameRate=\"25\"", "00:00:01:15"), 1_600);
+    assert_eq!(end("ttp:frameRate=\"25\"", "25f"), 1_000);
+    assert_eq!(
+        end(
+            "ttp:frameRate=\"24\" ttp:frameRateMultiplier=\"1000 1001\"",
+            "24f"
+        ),
+        1_001
+    );
+    assert_eq!(end("", "1.5h"), 5_400_000);
+    assert_eq!(end("", "2m"), 120_000);
+    assert_eq!(end("", "2.25s"), 2_250);
+    assert_eq!(end("", "250ms"), 250);
+    // 1 tick per second without a tick rate or frame rate
+    assert_eq!(end("", "3t"), 3_000);
+    assert_eq!(end("ttp:tickRate=\"10000000\"", "12345678t"), 1_235);
+    // Ticks are sub-frames when there's only a frame rate
+    assert_eq!(
+        end("ttp:frameRate=\"25\" ttp:subFrameRate=\"2\"", "25t"),
+        500
+    );
+
+    let kind = |time: &str| {
+        let ttml = format!("<tt><body><p begin=\"0s\" end=\"{}\"/></body></tt>", time);
+        ttml::from_str(&ttml).unwrap_err().kind
+    };
+    for time in &[
+        "",
+        "1",
+        "1.s",
+        ".5s",
+        "1:00:00.000",
+        "00:60:00.000",
+        "00:00:60.000",
+        "00:00:1.000",
+        "00:00:00,000",
+        "00:00:00:xx",
+        "00:00:00:01.x",
+        "99999999999999999999:00:00",
+        "9999999999999999:00:00",
+        "1.5d",
+        "100h",
+    ] {
+        assert_eq!(kind(time), ErrorKind::InvalidTtmlTime, "{:?}", time);
+    }
+}
+
+#[test]
+fn export() {
+    let subtitles = vec![
+        Subtitle {
+            start: Timestamp::from_millis(1_500),
+            duration: Duration::from_millis(2_000),
+            text: String::from("<i>Fish & chips</i>\n<font color=\"red\">-></font> \"quoted\""),
+            position: None,
+            id: None,
+        },
+        Subtitle {
+            start: Timestamp::from_millis(4_000),
+            duration: Duration::from_millis(1_000),
+            text: String::from("<i>Overlapping <b>tags</i> are</b> <u>reopened\nacross lines"),
+            position: None,
+            id: Some(10),
+        },
+        Subtitle {
+            start: Timestamp::from_millis(5_000),
+            duration: Duration::from_millis(1_000),
+            text: String::from("Stray </i>closing <3"),
+            position: None,
+            id: None,
+        },
+    ];
+
+    let rendered = RenderOptions::new().lang("en-US").render(&subtitles);
+    insta::assert_snapshot!(rendered);
+
+    let mut io_rendered = Vec::new();
+    RenderOptions::new()
+        .lang("en-US")
+        .render_to_writer(&mut io_rendered, &subtitles)
+        .unwrap();
+    assert_eq!(String::from_utf8(io_rendered).unwrap(), rendered);
+
+    assert!(ttml::to_string(&subtitles).contains(" xml:lang=\"\">"));
+    assert!(RenderOptions::new()
+        .lang("\"><")
+        .render(&[])
+        .contains(" xml:lang=\"&quot;&gt;&lt;\">"));
+}
+
+#[test]
+fn round_trip() {
+    let subtitles = ttml::from_str(DFXP).unwrap();
+    let rendered = ttml::to_string(&subtitles);
+    assert_eq!(ttml::from_str(&rendered).unwrap(), subtitles);
+}
+
+#[test]
+fn escaped_tags_stay_escaped() {
+    let ttml =
+        r#"<tt><body><p begin="0s" end="1s">&lt;b&gt;Not bold&#60;/b&gt; 1 &lt; 2</p></body></tt>"#;
+    let subtitles = ttml::from_str(ttml).unwrap();
+    assert_eq!(subtitles[0].text, "&lt;b>Not bold&lt;/b> 1 < 2");
+    assert_eq!(
+        kiss_srt::to_string(&subtitles),
+        "1\n00:00:00,000 --> 00:00:01,000\n&lt;b>Not bold&lt;/b> 1 < 2\n",
+    );
+}
+
+#[test]
+fn import_error_kinds() {
+    let kind = |text| ttml::from_str(text).unwrap_err().kind;
+    assert_eq!(
+        kind("<tt><body><p begin=\"2s\" end=\"1s\">Backwards</p></body></tt>"),
+        ErrorKind::TimestampEndBeforeStart
+    );
+    assert_eq!(kind(""), ErrorKind::InvalidTtmlRoot);
+    assert_eq!(kind("<tt/><tt/>"), ErrorKind::InvalidTtmlRoot);
+    assert_eq!(kind("<tt><body>"), ErrorKind::InvalidXml);
+    assert_eq!(kind("<tt><!-- unclosed </tt>"), ErrorKind::InvalidXml);
+    assert_eq!(kind("<tt><p begin=1s end=2s/></tt>"), ErrorKind::InvalidXml);
+    assert_eq!(
+        kind("<tt><p begin=\"1s\" end=\"2s\"><span>Unclosed</p></tt>"),
+        ErrorKind::InvalidXml
+    );
+}
+
+const SRT: &str = "\
+1
+00:00:00,000 --> 00:00:01,000
+This is SRT
+";
+
+const MISMATCHED: &str = r#"<tt xmlns="http://www.w3.org/ns/ttml">
+  <body>
+    <div>
+  </body>
+</tt>
+"#;
+
+const UNQUOTED: &str = r#"<tt xmlns="http://www.w3.org/ns/ttml">
+  <body>
+    <p begin="1s" end=2s>Text</p>
+  </body>
+</tt>
+"#;
+
+const MISSING_TIMING: &str = r#"<tt xmlns="http://www.w3.org/ns/ttml">
+  <body>
+    <div><p begin="1s">No end</p></div>
+  </body>
+</tt>
+"#;
+
+const INVALID_TIME: &str = r#"<tt xmlns="http://www.w3.org/ns/ttml">
+  <body>
+    <p begin="00:00:01,000" end="00:00:02.000">SRT style</p>
+  </body>
+</tt>
+"#;
+
+snapshot_import_err!(
+    (not_ttml, SRT),
+    (mismatched_end_tag, MISMATCHED),
+    (unquoted_attribute, UNQUOTED),
+    (missing_timing, MISSING_TIMING),
+    (invalid_time, INVALID_TIME),
+);
@@ -0,0 +1,176 @@
+macro_rules! snapshot_import_err {
+    ( $( ($fn_name:ident, $text:expr ) ),* $(,)? ) => {
+        $(
+        #[test]
+        fn $fn_name() {
+            let err = webvtt::from_str($text).unwrap_err();
+            ::insta::assert_snapshot!(err.to_string());
+            ::insta::assert_snapshot!(err.annotate($text).to_string());
+        }
+        )*
+    };
+}
+
+use kiss_srt::{error::ErrorKind, webvtt, Duration, Position, Subtitle, Timestamp};
+
+fn subtitle(start: u32, text: &str) -> Subtitle {
+    Subtitle {
+        start: Timestamp::from_millis(start),
+        duration: Duration::from_millis(1_250),
+        text: String::from(text),
+        position: None,
+        id: None,
+    }
+}
+
+#[test]
+fn export() {
+    let mut subtitles = vec![
+        subtitle(0, "<I>Shouting</I> & <B>bold</b>\n<u>under</u>line"),
+        subtitle(
+            3_600_000,
+            "<font color=\"#ff0000\">Red</font> <not a tag> a < b > c",
+        ),
+        subtitle(4_000_000, "Arrow --> in text"),
+    ];
+    subtitles[1].position = Some(Position {
+        x1: 1,
+        x2: 2,
+        y1: 3,
+        y2: 4,
+    });
+    subtitles[2].id = Some(10);
+
+    insta::assert_snapshot!(webvtt::to_string(&subtitles));
+    insta::assert_snapshot!(webvtt::RenderOptions::new()
+        .cue_ids(true)
+        .render(&subtitles));
+}
+
+#[test]
+fn writers_match_to_string() {
+    let subtitles = vec![subtitle(0, "One"), subtitle(2_000, "Two\nlines")];
+    let expected = webvtt::to_string(&subtitles);
+
+    let mut io_rendered = Vec::new();
+    webvtt::to_writer(&mut io_rendered, &subtitles).unwrap();
+    assert_eq!(String::from_utf8(io_rendered).unwrap(), expected);
+
+    let mut fmt_rendered = String::new();
+    webvtt::to_fmt_writer(&mut fmt_rendered, &subtitles).unwrap();
+    assert_eq!(fmt_rendered, expected);
+}
+
+#[test]
+fn nothing_to_export() {
+    assert_eq!(webvtt::to_string(&[]), "WEBVTT\n");
+}
+
+#[test]
+fn import() {
+    const VTT: &str = "\u{feff}WEBVTT\tTitle
+Kind: captions
+Language: en
+
+STYLE
+::cue { color: red }
+
+REGION
+id:fred
+
+NOTE
+A multi-line
+comment
+
+1
+00:00.500 --> 00:02.000 region:fred align:left
+<v.loud Bob>Hi &amp; welcome</v>
+<c.yellow>Colored</c> <i.whisper>text</i> &lt;3&nbsp;&unknown;
+
+00:02.000	-->	01:00:00.000
+<b>Karaoke</b> <00:00:02.500><u>timing</u>
+<v Alice></v>
+kept
+
+NOTE not a cue
+";
+
+    let subtitles = webvtt::from_str(VTT).unwrap();
+    insta::assert_debug_snapshot!(subtitles);
+}
+
+#[test]
+fn round_trip() {
+    let subtitles = vec![
+        subtitle(0, "<i>Fish & chips</i>\n<b>a < b > c</b>"),
+        subtitle(3_600_000, "Second cue"),
+    ];
+
+    for options in &[
+        webvtt::RenderOptions::new(),
+        webvtt::RenderOptions::new().cue_ids(true),
+    ] {
+        let vtt = options.render(&subtitles);
+        assert_eq!(webvtt::from_str(&vtt).unwrap(), subtitles);
+    }
+}
+
+#[test]
+fn escaped_tags_stay_escaped() {
+    let vtt = "WEBVTT\n\n00:00.000 --> 00:01.000\n&lt;i&gt;Not italic&lt;/i&gt; &lt;3\n";
+    let subtitles = webvtt::from_str(vtt).unwrap();
+    assert_eq!(subtitles[0].text, "&lt;i>Not italic&lt;/i> <3");
+    assert_eq!(
+        kiss_srt::to_string(&subtitles),
+        "1\n00:00:00,000 --> 00:00:01,000\n&lt;i>Not italic&lt;/i> <3\n",
+    );
+    // Nothing reads as formatting once it's back in SRT
+    let srt = kiss_srt::from_str(&kiss_srt::to_string(&subtitles)).unwrap();
+    assert!(!srt[0].text.contains("<i>"));
+}
+
+#[test]
+fn import_error_kinds() {
+    let kind = |text| webvtt::from_str(text).unwrap_err().kind;
+    assert_eq!(kind(""), ErrorKind::InvalidWebVttHeader);
+    assert_eq!(kind("WEBVTTX\n"), ErrorKind::InvalidWebVttHeader);
+    assert_eq!(kind("WEBVTT\n\nid\n"), ErrorKind::MissingCueTiming);
+    assert_eq!(
+        kind("WEBVTT\n\n00:01.000 -->00:02.000\n"),
+        ErrorKind::InvalidTimestampDivider
+    );
+    assert_eq!(
+        kind("WEBVTT\n\n00:02.000 --> 00:01.000\n"),
+        ErrorKind::TimestampEndBeforeStart
+    );
+    assert_eq!(
+        kind("WEBVTT\n\n60:00.000 --> 61:00.000\n"),
+        ErrorKind::InvalidWebVttTimestamp
+    );
+}
+
+const NO_HEADER: &str = "\
+1
+00:00:00,000 --> 00:00:01,000
+This is SRT
+";
+
+const NO_TIMING: &str = "\
+WEBVTT
+
+identifier
+Text without a timing line
+";
+
+const COMMA_MILLIS: &str = "\
+WEBVTT
+
+00:00:00,000 --> 00:00:01.000
+SRT style timestamp
+";
+
+snapshot_import_err!(
+    (missing_header, NO_HEADER),
+    (missing_timing, NO_TIMING),
+    (comma_millis, COMMA_MILLIS),
+);